@@ -0,0 +1,76 @@
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+
+use q88_merge_sorted_array::impl_v1::Solution as Q88_v1;
+use q88_merge_sorted_array::impl_v2::Solution as Q88_v2;
+use q88_merge_sorted_array::impl_v3::Solution as Q88_v3;
+use q1574_shortest_subarray_to_be_removed_to_make_array_sorted::impl_v1::Solution as Q1574_v1;
+use q1574_shortest_subarray_to_be_removed_to_make_array_sorted::impl_v2::Solution as Q1574_v2;
+use q1574_shortest_subarray_to_be_removed_to_make_array_sorted::impl_v3::Solution as Q1574_v3;
+
+// one byte per element, so equal neighbours and plateaus are common
+fn decode_array(u: &mut Unstructured, max_len: usize) -> Result<Vec<i32>> {
+    let len = u.int_in_range(0..=max_len)?;
+
+    (0..len)
+        .map(|_| u.arbitrary::<u8>().map(i32::from))
+        .collect()
+}
+
+// 1 <= arr.length <= 10^5, 0 <= arr[i] <= 10^9
+fn check_q1574(arr: Vec<i32>) {
+    if arr.is_empty() {
+        return;
+    }
+
+    let result_v1 = Q1574_v1::find_length_of_shortest_subarray(arr.clone());
+    let result_v2 = Q1574_v2::find_length_of_shortest_subarray(arr.clone());
+    let result_v3 = Q1574_v3::find_length_of_shortest_subarray(arr.clone());
+
+    assert_eq!(result_v1, result_v2, "v1 and v2 differ on {:?}", arr);
+    assert_eq!(result_v1, result_v3, "v1 and v3 differ on {:?}", arr);
+}
+
+// 0 <= m, n <= 200, 1 <= m + n, both sorted
+fn check_q88(mut nums1: Vec<i32>, mut nums2: Vec<i32>) {
+    if nums1.is_empty() && nums2.is_empty() {
+        return;
+    }
+
+    nums1.sort_unstable();
+    nums2.sort_unstable();
+
+    let (m, n) = (nums1.len(), nums2.len());
+
+    let mut expected = [nums1.as_slice(), nums2.as_slice()].concat();
+    expected.sort_unstable();
+
+    nums1.resize(m + n, 0);
+
+    let mut result_v1 = nums1.clone();
+    Q88_v1::merge(&mut result_v1, m as i32, &nums2, n as i32);
+    assert_eq!(result_v1, expected, "v1 on {:?} and {:?}", nums1, nums2);
+
+    let mut result_v2 = nums1.clone();
+    Q88_v2::merge(&mut result_v2, m as i32, &nums2, n as i32);
+    assert_eq!(result_v2, expected, "v2 on {:?} and {:?}", nums1, nums2);
+
+    let mut result_v3 = nums1.clone();
+    Q88_v3::merge(&mut result_v3, m, &nums2, n);
+    assert_eq!(result_v3, expected, "v3 on {:?} and {:?}", nums1, nums2);
+}
+
+fn run(data: &[u8]) -> Result<()> {
+    let mut u = Unstructured::new(data);
+
+    check_q1574(decode_array(&mut u, 1_000)?);
+    check_q88(decode_array(&mut u, 200)?, decode_array(&mut u, 200)?);
+
+    Ok(())
+}
+
+fuzz_target!(|data: &[u8]| {
+    let _ = run(data);
+});
@@ -0,0 +1,89 @@
+#![no_main]
+
+use cache_util::{Cache, CacheImplementation, CacheOperation, LFUModel, LRUModel, LeetCodeCache};
+use libfuzzer_sys::arbitrary::{Result, Unstructured};
+use libfuzzer_sys::fuzz_target;
+
+type Caches = Vec<(String, Box<dyn LeetCodeCache>)>;
+
+fn caches(
+    implementations: impl Iterator<Item = &'static CacheImplementation>,
+    capacity: usize,
+) -> Caches {
+    implementations
+        .map(|implementation| (implementation.id(), (implementation.new)(capacity as i32)))
+        .collect()
+}
+
+// Small capacities and few keys compared to them, so capacity 1, repeated keys,
+// updates and evictions come up in almost every input
+fn decode(data: &[u8]) -> Result<(usize, Vec<CacheOperation>)> {
+    let mut u = Unstructured::new(data);
+
+    let capacity = u.int_in_range(1..=64usize)?;
+    let max_key = capacity as i32 * 2;
+
+    let mut operations = Vec::new();
+    while !u.is_empty() {
+        let key = u.int_in_range(0..=max_key)?;
+
+        operations.push(match u.arbitrary()? {
+            true => CacheOperation::Put {
+                key,
+                value: u.int_in_range(0..=100_000)?,
+            },
+            false => CacheOperation::Get { key },
+        });
+    }
+
+    Ok((capacity, operations))
+}
+
+// every implementation against the reference model, structure validated after each operation
+fn cross_check(model: &mut dyn Cache, caches: &mut Caches, operations: &[CacheOperation]) {
+    for operation in operations {
+        match *operation {
+            CacheOperation::Put { key, value } => {
+                model.put(key, value);
+                for (_, cache) in caches.iter_mut() {
+                    cache.put(key, value);
+                }
+            }
+            CacheOperation::Get { key } => {
+                let expected = model.get(&key).unwrap_or(-1);
+                for (name, cache) in caches.iter_mut() {
+                    assert_eq!(
+                        cache.get(key),
+                        expected,
+                        "{} differs from the model on get({})",
+                        name,
+                        key
+                    );
+                }
+            }
+        }
+
+        let len = caches[0].1.len();
+        for (name, cache) in caches.iter() {
+            assert_eq!(cache.len(), len, "{} has a different len", name);
+            cache.debug_validate();
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok((capacity, operations)) = decode(data) else {
+        return;
+    };
+
+    cross_check(
+        &mut LRUModel::new(capacity),
+        &mut caches(q146_lru_cache::implementations(), capacity),
+        &operations,
+    );
+    cross_check(
+        &mut LFUModel::new(capacity),
+        &mut caches(q460_lfu_cache::implementations(), capacity),
+        &operations,
+    );
+});
@@ -0,0 +1,3 @@
+use cache_util::*;
+
+define_benchmark!(q146_lru_cache, arena, LRUCache);
@@ -0,0 +1,31 @@
+use cache_util::*;
+use criterion::{criterion_group, criterion_main};
+
+bench_cache!(
+    q146_lru_cache_bench_d_ary_heap_4,
+    q146_lru_cache::d_ary_heap::LRUCache<4>
+);
+
+bench_cache!(
+    q146_lru_cache_bench_d_ary_heap_eviction_4,
+    q146_lru_cache::d_ary_heap::LRUEvictionCache<4>
+);
+
+bench_cache!(
+    q146_lru_cache_bench_d_ary_heap_8,
+    q146_lru_cache::d_ary_heap::LRUCache<8>
+);
+
+bench_cache!(
+    q146_lru_cache_bench_d_ary_heap_eviction_8,
+    q146_lru_cache::d_ary_heap::LRUEvictionCache<8>
+);
+
+criterion_group!(
+    benches,
+    q146_lru_cache_bench_d_ary_heap_4,
+    q146_lru_cache_bench_d_ary_heap_eviction_4,
+    q146_lru_cache_bench_d_ary_heap_8,
+    q146_lru_cache_bench_d_ary_heap_eviction_8,
+);
+criterion_main!(benches);
@@ -0,0 +1,3 @@
+use cache_util::*;
+
+bench_latency!(q146_lru_cache);
@@ -0,0 +1,3 @@
+use cache_util::*;
+
+bench_memory!(q146_lru_cache);
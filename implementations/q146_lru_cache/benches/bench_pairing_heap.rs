@@ -0,0 +1,19 @@
+use cache_util::*;
+use criterion::{criterion_group, criterion_main};
+
+bench_cache!(
+    q146_lru_cache_bench_pairing_heap,
+    q146_lru_cache::pairing_heap::LRUCache
+);
+
+bench_cache!(
+    q146_lru_cache_bench_pairing_heap_eviction,
+    q146_lru_cache::pairing_heap::LRUEvictionCache
+);
+
+criterion_group!(
+    benches,
+    q146_lru_cache_bench_pairing_heap,
+    q146_lru_cache_bench_pairing_heap_eviction,
+);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+use crate::vec_hashmap::{DAryLRUCache, DAryLRUEvictionCache};
+
+// vec_hashmap with a wider heap: shallower, and a node's children share cache lines
+pub type LRUCache<const D: usize> = DAryLRUCache<D>;
+pub type LRUEvictionCache<const D: usize> = DAryLRUEvictionCache<D>;
+
+// the binary heap is vec_hashmap's
+cache_util::register_caches!("q146";
+    "d_ary_heap_4" => LRUCache<4>,
+    "d_ary_heap_4_eviction" => LRUEvictionCache<4>,
+    "d_ary_heap_8" => LRUCache<8>,
+    "d_ary_heap_8_eviction" => LRUEvictionCache<8>,
+);
+
+/*
+ * Your LRUCache object will be instantiated and called as such:
+ * let obj = LRUCache::<4>::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
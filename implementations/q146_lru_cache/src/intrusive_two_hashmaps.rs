@@ -67,9 +67,7 @@ impl LRUCache {
             self.update(node_rc);
         } else {
             if self.map.len() == self.capacity {
-                if let Some(node_rc) = self.freq_list.pop_front() {
-                    self.map.remove(&node_rc.key);
-                }
+                self.evict();
             }
 
             let node_rc = Rc::new(Node::new(key, value));
@@ -79,6 +77,24 @@ impl LRUCache {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    fn evict(&mut self) {
+        if let Some(node_rc) = self.freq_list.pop_front() {
+            self.map.remove(&node_rc.key);
+        }
+    }
+
     /// Removes a node from its current frequency list.
     ///
     /// # Safety
@@ -109,6 +125,64 @@ impl LRUCache {
     }
 }
 
+#[cfg(feature = "validate")]
+impl LRUCache {
+    // both directions see the same nodes, every listed node is the one in map
+    pub fn debug_validate(&self) {
+        let mut listed = 0;
+        for node in self.freq_list.iter() {
+            assert!(
+                self.map
+                    .get(&node.key)
+                    .is_some_and(|n| std::ptr::eq(n.as_ref(), node)),
+                "map[{}] isn't the listed node",
+                node.key
+            );
+            listed += 1;
+        }
+
+        assert_eq!(
+            self.freq_list.iter().rev().count(),
+            listed,
+            "asymmetric links"
+        );
+        assert_eq!(listed, self.map.len(), "keys missing from the list");
+        assert!(self.map.len() <= self.capacity, "over capacity");
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LRUCache {
+    // the list from least to most recently used, node ids in list order
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("intrusive_two_hashmaps");
+
+        let listed = self.freq_list.iter().collect::<Vec<_>>();
+        dump.push_list(
+            None,
+            listed
+                .iter()
+                .enumerate()
+                .map(|(id, node)| (id, node.key, format!("val {}", node.val.get()))),
+        );
+
+        for (&key, node_rc) in &self.map {
+            if let Some(id) = listed
+                .iter()
+                .position(|&n| std::ptr::eq(n, node_rc.as_ref()))
+            {
+                dump.index.insert(key, id);
+            }
+        }
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q146";
+    "intrusive_two_hashmaps" => LRUCache,
+);
+
 /*
  * Your LRUCache object will be instantiated and called as such:
  * let obj = LRUCache::new(capacity);
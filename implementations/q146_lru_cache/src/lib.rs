@@ -1,4 +1,25 @@
+pub mod arena;
+pub mod d_ary_heap;
 pub mod intrusive_two_hashmaps;
+pub mod pairing_heap;
 pub mod priority_queue;
 pub mod two_hashmaps;
+pub mod unsafe_linked_list;
 pub mod vec_hashmap;
+
+// every variant the modules above register, see cache_util::register_caches!; a module left
+// out here fails test_every_module_is_registered
+pub fn implementations() -> impl Iterator<Item = &'static cache_util::CacheImplementation> {
+    [
+        arena::IMPLEMENTATIONS,
+        d_ary_heap::IMPLEMENTATIONS,
+        intrusive_two_hashmaps::IMPLEMENTATIONS,
+        pairing_heap::IMPLEMENTATIONS,
+        priority_queue::IMPLEMENTATIONS,
+        two_hashmaps::IMPLEMENTATIONS,
+        unsafe_linked_list::IMPLEMENTATIONS,
+        vec_hashmap::IMPLEMENTATIONS,
+    ]
+    .into_iter()
+    .flatten()
+}
@@ -32,6 +32,8 @@ impl LRUCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache);
 }
 
 impl LRUEvictionCache {
@@ -43,6 +45,7 @@ impl LRUEvictionCache {
             ),
         }
     }
+
     pub fn put(&mut self, key: i32, value: i32) {
         self.cache.put(key, value);
     }
@@ -50,8 +53,15 @@ impl LRUEvictionCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache);
 }
 
+cache_util::register_caches!("q146";
+    "priority_queue" => LRUCache,
+    "priority_queue_eviction" => LRUEvictionCache,
+);
+
 /*
  * Your LRUCache object will be instantiated and called as such:
  * let obj = LRUCache::new(capacity);
@@ -143,9 +143,7 @@ impl LRUCache {
             }
             _ => {
                 if self.map.len() == self.capacity {
-                    if let Some(node_rc) = self.freq_list.pop_front() {
-                        self.map.remove(&node_rc.borrow().key);
-                    }
+                    self.evict();
                 }
 
                 let node_rc = Rc::new(RefCell::new(Node::new(key, value)));
@@ -156,12 +154,111 @@ impl LRUCache {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    fn evict(&mut self) {
+        if let Some(node_rc) = self.freq_list.pop_front() {
+            self.map.remove(&node_rc.borrow().key);
+        }
+    }
+
     fn update(&mut self, node_rc: Rc<RefCell<Node>>) {
         self.freq_list.remove(node_rc.clone());
         self.freq_list.push_back(&node_rc);
     }
 }
 
+#[cfg(feature = "validate")]
+impl LRUCache {
+    // prev/next links are symmetric, every listed node is the one in map
+    pub fn debug_validate(&self) {
+        let same = |a: &Option<Rc<RefCell<Node>>>, b: &Option<Rc<RefCell<Node>>>| match (a, b) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+
+        let mut listed = 0;
+        let mut prev = None;
+        let mut curr = self.freq_list.head.clone();
+
+        while let Some(node_rc) = curr {
+            let node = node_rc.borrow();
+
+            let node_prev = node.prev.as_ref().and_then(Weak::upgrade);
+            assert!(
+                same(&node_prev, &prev),
+                "prev of {} doesn't match",
+                node.key
+            );
+            assert!(
+                self.map
+                    .get(&node.key)
+                    .is_some_and(|n| Rc::ptr_eq(n, &node_rc)),
+                "map[{}] isn't the listed node",
+                node.key
+            );
+
+            listed += 1;
+            curr = node.next.clone();
+
+            drop(node);
+            prev = Some(node_rc);
+        }
+
+        assert!(
+            same(&self.freq_list.tail, &prev),
+            "tail isn't the last node"
+        );
+        assert_eq!(listed, self.map.len(), "keys missing from the list");
+        assert!(self.map.len() <= self.capacity, "over capacity");
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LRUCache {
+    // the list from least to most recently used, node ids in list order; the walk is bounded
+    // by the capacity in case the links are broken
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("two_hashmaps");
+
+        let listed = std::iter::successors(self.freq_list.head.clone(), |node_rc| {
+            node_rc.borrow().next.clone()
+        })
+        .take(self.capacity)
+        .collect::<Vec<_>>();
+
+        dump.push_list(
+            None,
+            listed.iter().enumerate().map(|(id, node_rc)| {
+                let node = node_rc.borrow();
+                (id, node.key, format!("val {}", node.val))
+            }),
+        );
+
+        for (&key, node_rc) in &self.map {
+            if let Some(id) = listed.iter().position(|n| Rc::ptr_eq(n, node_rc)) {
+                dump.index.insert(key, id);
+            }
+        }
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q146";
+    "two_hashmaps" => LRUCache,
+);
+
 /*
  * Your LRUCache object will be instantiated and called as such:
  * let obj = LRUCache::new(capacity);
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+type Link = Option<NonNull<Node>>;
+
+#[derive(Debug)]
+struct Node {
+    key: i32,
+    val: i32,
+
+    prev: Link,
+    next: Link,
+}
+
+// doubly-linked list of raw pointers into the slab, no ownership
+#[derive(Debug)]
+struct FreqList {
+    head: Link,
+    tail: Link,
+}
+
+impl FreqList {
+    pub fn new() -> Self {
+        FreqList {
+            head: None,
+            tail: None,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `node` must point to an initialized slab node that is not linked in any list.
+    pub unsafe fn push_back(&mut self, mut node: NonNull<Node>) {
+        unsafe {
+            node.as_mut().prev = self.tail;
+            node.as_mut().next = None;
+
+            match self.tail {
+                Some(mut tail) => tail.as_mut().next = Some(node),
+                None => self.head = Some(node),
+            }
+        }
+
+        self.tail = Some(node);
+    }
+
+    /// # Safety
+    ///
+    /// All nodes linked in this list must be initialized slab nodes.
+    pub unsafe fn pop_front(&mut self) -> Link {
+        let head = self.head?;
+        unsafe { self.remove(head) };
+        Some(head)
+    }
+
+    /// # Safety
+    ///
+    /// `node` must point to an initialized slab node that is linked in this list.
+    pub unsafe fn remove(&mut self, mut node: NonNull<Node>) {
+        unsafe {
+            let (prev, next) = (node.as_ref().prev, node.as_ref().next);
+
+            match prev {
+                Some(mut prev) => prev.as_mut().next = next,
+                None => self.head = next,
+            }
+
+            match next {
+                Some(mut next) => next.as_mut().prev = prev,
+                None => self.tail = prev,
+            }
+
+            node.as_mut().prev = None;
+            node.as_mut().next = None;
+        }
+    }
+}
+
+pub struct LRUCache {
+    map: HashMap<i32, NonNull<Node>>, // key -> node in slab
+    slab: NonNull<Node>,              // single owning allocation of `capacity` nodes
+    used: usize,                      // number of slab nodes initialized so far
+    freq_list: FreqList,              // list of nodes, ordered by last access time
+    capacity: usize,                  // as HashMap's capacity() could be auto-resized
+}
+
+/**
+ * `&self` means the method takes an immutable reference.
+ * If you need a mutable reference, change it to `&mut self` instead.
+ */
+impl LRUCache {
+    pub fn new(capacity: i32) -> Self {
+        let slab = Box::<[Node]>::new_uninit_slice(capacity as usize);
+
+        Self {
+            map: HashMap::with_capacity(capacity as usize),
+            slab: NonNull::from(Box::leak(slab)).cast::<Node>(),
+            used: 0,
+            freq_list: FreqList::new(),
+            capacity: capacity as usize,
+        }
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        match self.map.get(&key).copied() {
+            // SAFETY: every node in map is initialized and linked in freq_list
+            Some(node) => unsafe {
+                self.update(node);
+                node.as_ref().val
+            },
+            _ => -1,
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        match self.map.get(&key).copied() {
+            // SAFETY: every node in map is initialized and linked in freq_list
+            Some(mut node) => unsafe {
+                node.as_mut().val = value;
+                self.update(node);
+            },
+            _ => {
+                let node = if self.map.len() == self.capacity {
+                    // reuse the evicted node in place, the slab is already full
+                    // SAFETY: freq_list only links initialized nodes, and is non-empty here
+                    unsafe {
+                        let node = self.freq_list.pop_front().expect("cache is full");
+                        self.map.remove(&node.as_ref().key);
+                        node
+                    }
+                } else {
+                    // SAFETY: used < capacity, so the slot is inside the slab
+                    let node = unsafe { self.slab.add(self.used) };
+                    self.used += 1;
+                    node
+                };
+
+                // SAFETY: `node` is inside the slab and no longer linked anywhere
+                unsafe {
+                    node.write(Node {
+                        key,
+                        val: value,
+                        prev: None,
+                        next: None,
+                    });
+                    self.freq_list.push_back(node);
+                    self.map.insert(key, node);
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    /// # Safety
+    ///
+    /// `node` must be an initialized node linked in `freq_list`.
+    unsafe fn update(&mut self, node: NonNull<Node>) {
+        unsafe {
+            self.freq_list.remove(node);
+            self.freq_list.push_back(node);
+        }
+    }
+}
+
+impl Drop for LRUCache {
+    fn drop(&mut self) {
+        // SAFETY: `slab` came from Box::leak() with exactly `capacity` elements,
+        // and Node needs no drop glue, so releasing the uninit slice is enough
+        unsafe {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                self.slab.as_ptr() as *mut MaybeUninit<Node>,
+                self.capacity,
+            )));
+        }
+    }
+}
+
+#[cfg(feature = "validate")]
+impl LRUCache {
+    // prev/next links are symmetric, map points at the listed nodes inside the slab
+    pub fn debug_validate(&self) {
+        let slab = self.slab.as_ptr()..self.slab.as_ptr().wrapping_add(self.used);
+
+        let mut listed = 0;
+        let (mut prev, mut curr) = (None, self.freq_list.head);
+
+        while let Some(node) = curr {
+            assert!(slab.contains(&node.as_ptr()), "node outside the slab");
+
+            // SAFETY: node is one of the `used` initialized slab nodes, checked above
+            let node_ref = unsafe { node.as_ref() };
+
+            assert_eq!(
+                node_ref.prev, prev,
+                "prev of {} doesn't match",
+                node_ref.key
+            );
+            assert_eq!(
+                self.map.get(&node_ref.key),
+                Some(&node),
+                "map[{}] isn't the listed node",
+                node_ref.key
+            );
+
+            listed += 1;
+            (prev, curr) = (Some(node), node_ref.next);
+        }
+
+        assert_eq!(self.freq_list.tail, prev, "tail isn't the last node");
+        assert_eq!(listed, self.map.len(), "keys missing from the list");
+        assert!(self.used <= self.capacity, "slab overflow");
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LRUCache {
+    fn slab_index(&self, node: NonNull<Node>) -> usize {
+        (node.as_ptr() as usize - self.slab.as_ptr() as usize) / std::mem::size_of::<Node>()
+    }
+
+    // the list from least to most recently used, node ids being slab indices; the walk stops
+    // at a node outside the slab, in case the links are broken
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("unsafe_linked_list");
+        let slab = self.slab.as_ptr()..self.slab.as_ptr().wrapping_add(self.used);
+        let in_slab = |node: &NonNull<Node>| slab.contains(&node.as_ptr());
+
+        let listed = std::iter::successors(self.freq_list.head.filter(in_slab), |node| {
+            // SAFETY: in_slab() lets only initialized slab nodes through
+            unsafe { node.as_ref() }.next.filter(in_slab)
+        })
+        .take(self.capacity);
+
+        dump.push_list(
+            None,
+            listed.map(|node| {
+                // SAFETY: in_slab() lets only initialized slab nodes through
+                let node_ref = unsafe { node.as_ref() };
+                (
+                    self.slab_index(node),
+                    node_ref.key,
+                    format!("val {}", node_ref.val),
+                )
+            }),
+        );
+
+        dump.index.extend(
+            self.map
+                .iter()
+                .map(|(&key, &node)| (key, self.slab_index(node))),
+        );
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q146";
+    "unsafe_linked_list" => LRUCache,
+);
+
+/*
+ * Your LRUCache object will be instantiated and called as such:
+ * let obj = LRUCache::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
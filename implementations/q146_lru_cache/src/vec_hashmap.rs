@@ -1,24 +1,28 @@
 use cache_util::HashMapStorage;
 use cache_util::{Cache, EvictionCache, GenericCache};
-use cache_util::{EvictionPolicyVHM, KeyAwareHeapNode, LRUHeapNode, ValueAwareHeapNode};
+use cache_util::{EvictionPolicyVHM, LRUHeapNode, ValueAwareHeapNode};
 
-pub struct LRUCache {
-    cache: GenericCache<EvictionPolicyVHM<KeyAwareHeapNode<LRUHeapNode>>, HashMapStorage>,
+// generic over the heap's arity, d_ary_heap having the 4-ary and 8-ary ones
+pub struct DAryLRUCache<const D: usize> {
+    cache: GenericCache<EvictionPolicyVHM<LRUHeapNode, D>, HashMapStorage>,
 }
 
-pub struct LRUEvictionCache {
-    cache: EvictionCache<EvictionPolicyVHM<ValueAwareHeapNode<KeyAwareHeapNode<LRUHeapNode>>>>,
+pub struct DAryLRUEvictionCache<const D: usize> {
+    cache: EvictionCache<EvictionPolicyVHM<ValueAwareHeapNode<LRUHeapNode>, D>>,
 }
 
+pub type LRUCache = DAryLRUCache<2>;
+pub type LRUEvictionCache = DAryLRUEvictionCache<2>;
+
 /**
  * `&self` means the method takes an immutable reference.
  * If you need a mutable reference, change it to `&mut self` instead.
  */
-impl LRUCache {
+impl<const D: usize> DAryLRUCache<D> {
     pub fn new(capacity: i32) -> Self {
         Self {
             cache: GenericCache::new(
-                EvictionPolicyVHM::<KeyAwareHeapNode<LRUHeapNode>>::new(),
+                EvictionPolicyVHM::<LRUHeapNode, D>::new(),
                 HashMapStorage::new(capacity as usize),
                 capacity as usize,
             ),
@@ -32,17 +36,20 @@ impl LRUCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache, batch);
 }
 
-impl LRUEvictionCache {
+impl<const D: usize> DAryLRUEvictionCache<D> {
     pub fn new(capacity: i32) -> Self {
         Self {
             cache: EvictionCache::new(
-                EvictionPolicyVHM::<ValueAwareHeapNode<KeyAwareHeapNode<LRUHeapNode>>>::new(),
+                EvictionPolicyVHM::<ValueAwareHeapNode<LRUHeapNode>, D>::new(),
                 capacity as usize,
             ),
         }
     }
+
     pub fn put(&mut self, key: i32, value: i32) {
         self.cache.put(key, value);
     }
@@ -50,8 +57,15 @@ impl LRUEvictionCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache, batch);
 }
 
+cache_util::register_caches!("q146";
+    "vec_hashmap" => LRUCache,
+    "vec_hashmap_eviction" => LRUEvictionCache,
+);
+
 /*
  * Your LRUCache object will be instantiated and called as such:
  * let obj = LRUCache::new(capacity);
@@ -1,4 +1,7 @@
 use proptest::prelude::*;
+use std::panic::AssertUnwindSafe;
+#[cfg(feature = "dump")]
+use std::path::Path;
 
 use cache_util::*;
 
@@ -9,69 +12,87 @@ fn operation_sequence_strategy() -> impl Strategy<Value = Vec<CacheOperation>> {
 }
 
 fn test_lru_cache_with_operations(capacity: usize, operations: Vec<CacheOperation>) {
-    use q146_lru_cache::intrusive_two_hashmaps::LRUCache as LRUCache_intrusive_two_hashmaps;
-    use q146_lru_cache::priority_queue::LRUCache as LRUCache_priority_queue;
-    use q146_lru_cache::priority_queue::LRUEvictionCache as LRUEvictionCache_priority_queue;
-    use q146_lru_cache::two_hashmaps::LRUCache as LRUCache_two_hashmaps;
-    use q146_lru_cache::vec_hashmap::LRUCache as LRUCache_vec_hashmap;
-    use q146_lru_cache::vec_hashmap::LRUEvictionCache as LRUEvictionCache_vec_hashmap;
-
-    let mut cache_priority_queue = LRUCache_priority_queue::new(capacity as i32);
-    let mut cache_priority_queue_eviction = LRUEvictionCache_priority_queue::new(capacity as i32);
-    let mut cache_vec_hashmap = LRUCache_vec_hashmap::new(capacity as i32);
-    let mut cache_vec_hashmap_eviction = LRUEvictionCache_vec_hashmap::new(capacity as i32);
-    let mut cache_two_hashmaps = LRUCache_two_hashmaps::new(capacity as i32);
-    let mut cache_intrusive_two_hashmaps = LRUCache_intrusive_two_hashmaps::new(capacity as i32);
-
-    for operation in operations {
-        match operation {
+    let mut caches = q146_lru_cache::implementations()
+        .map(|implementation| (implementation.name, (implementation.new)(capacity as i32)))
+        .collect::<Vec<_>>();
+
+    for (step, operation) in operations.iter().enumerate() {
+        match *operation {
             CacheOperation::Put { key, value } => {
-                cache_priority_queue.put(key, value);
-                cache_priority_queue_eviction.put(key, value);
-                cache_vec_hashmap.put(key, value);
-                cache_vec_hashmap_eviction.put(key, value);
-                cache_two_hashmaps.put(key, value);
-                cache_intrusive_two_hashmaps.put(key, value);
+                for (_, cache) in caches.iter_mut() {
+                    cache.put(key, value);
+                }
             }
             CacheOperation::Get { key } => {
-                let result_priority_queue = cache_priority_queue.get(key);
-                let result_priority_queue_eviction = cache_priority_queue_eviction.get(key);
-                let result_vec_hashmap = cache_vec_hashmap.get(key);
-                let result_vec_hashmap_eviction = cache_vec_hashmap_eviction.get(key);
-                let result_two_hashmaps = cache_two_hashmaps.get(key);
-                let result_intrusive_two_hashmaps = cache_intrusive_two_hashmaps.get(key);
+                let results = caches
+                    .iter_mut()
+                    .map(|(name, cache)| (*name, cache.get(key)))
+                    .collect::<Vec<_>>();
 
                 // Compare results
-                assert_eq!(
-                    result_priority_queue, result_priority_queue_eviction,
-                    "priority_queue and priority_queue_eviction differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_vec_hashmap,
-                    "priority_queue and vec_hashmap differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_vec_hashmap_eviction,
-                    "priority_queue and vec_hashmap_eviction differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_two_hashmaps,
-                    "priority_queue and two_hashmaps differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_intrusive_two_hashmaps,
-                    "priority_queue and intrusive_two_hashmaps differ on get({})",
-                    key
+                let (first, expected) = results[0];
+                for &(name, result) in &results[1..] {
+                    assert_eq!(
+                        expected,
+                        result,
+                        "{} and {} differ on get({}){}",
+                        first,
+                        name,
+                        key,
+                        dump_step_of(&[first, name], capacity, &operations, step)
+                    );
+                }
+            }
+        }
+
+        // structural invariants, after every operation (a no-op without the validate feature)
+        for (name, cache) in &caches {
+            let validated = std::panic::catch_unwind(AssertUnwindSafe(|| cache.debug_validate()));
+            if let Err(panic) = validated {
+                eprintln!(
+                    "{} broke at step {}{}",
+                    name,
+                    step,
+                    dump_step_of(&[name], capacity, &operations, step)
                 );
+                std::panic::resume_unwind(panic);
             }
         }
     }
 }
 
+// with the dump feature, writes the structures of `names` right before and after
+// operations[step] and says where
+#[cfg(feature = "dump")]
+fn dump_step_of(
+    names: &[&str],
+    capacity: usize,
+    operations: &[CacheOperation],
+    step: usize,
+) -> String {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("dumps");
+
+    for implementation in q146_lru_cache::implementations().filter(|i| names.contains(&i.name)) {
+        write_step_dumps(&dir, implementation, capacity, operations, step).unwrap();
+    }
+
+    format!(", structures around it in {}", dir.display())
+}
+
+#[cfg(not(feature = "dump"))]
+fn dump_step_of(_: &[&str], _: usize, _: &[CacheOperation], _: usize) -> String {
+    String::new()
+}
+
+// a module calling register_caches! but missing from implementations() would skip every test
+#[test]
+fn test_every_module_is_registered() {
+    let src = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+    let missing = unregistered_modules(src, q146_lru_cache::implementations()).unwrap();
+
+    assert!(missing.is_empty(), "{:?} not in implementations()", missing);
+}
+
 #[test]
 fn test_lru_cache_implementations() {
     let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
@@ -79,3 +100,107 @@ fn test_lru_cache_implementations() {
         test_lru_cache_with_operations(capacity, operations);
     });
 }
+
+#[test]
+fn test_lru_cache_implementations_with_workloads() {
+    let provider = mock_operations_range_provider_default();
+
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |(capacity in capacity_range(), (_workload, operations) in workload_operations_strategy(provider.operations_range()))| {
+        test_lru_cache_with_operations(capacity, operations);
+    });
+}
+
+// the caches with a bulk path (a heap built once), by registered name
+const BULK_LOADED: &[(&str, FromIter)] = bulk_loaded![
+    "d_ary_heap_8" => q146_lru_cache::d_ary_heap::LRUCache<8>,
+    "d_ary_heap_4_eviction" => q146_lru_cache::d_ary_heap::LRUEvictionCache<4>,
+    "vec_hashmap" => q146_lru_cache::vec_hashmap::LRUCache,
+    "vec_hashmap_eviction" => q146_lru_cache::vec_hashmap::LRUEvictionCache,
+];
+
+#[test]
+fn test_lru_cache_bulk_load() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, items, operations) in bulk_load_strategy())| {
+        check_bulk_load(q146_lru_cache::implementations(), BULK_LOADED, capacity, &items, &operations);
+    });
+}
+
+#[test]
+fn test_lru_cache_batches() {
+    use q146_lru_cache::{d_ary_heap, vec_hashmap};
+
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, batches) in batches_strategy())| {
+        test_batches_with!(d_ary_heap::LRUCache<8>, capacity, &batches);
+        test_batches_with!(d_ary_heap::LRUEvictionCache<4>, capacity, &batches);
+        test_batches_with!(vec_hashmap::LRUCache, capacity, &batches);
+        test_batches_with!(vec_hashmap::LRUEvictionCache, capacity, &batches);
+    });
+}
+
+// LRU nodes have no access count
+#[test]
+fn test_lru_cache_introspection() {
+    use q146_lru_cache::{d_ary_heap, pairing_heap, priority_queue, vec_hashmap};
+
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in evicting_operations_strategy())| {
+        test_introspection_with!(d_ary_heap::LRUCache<8>, capacity, &operations, false);
+        test_introspection_with!(d_ary_heap::LRUEvictionCache<4>, capacity, &operations, false);
+        test_introspection_with!(pairing_heap::LRUCache, capacity, &operations, false);
+        test_introspection_with!(pairing_heap::LRUEvictionCache, capacity, &operations, false);
+        test_introspection_with!(priority_queue::LRUCache, capacity, &operations, false);
+        test_introspection_with!(priority_queue::LRUEvictionCache, capacity, &operations, false);
+        test_introspection_with!(vec_hashmap::LRUCache, capacity, &operations, false);
+        test_introspection_with!(vec_hashmap::LRUEvictionCache, capacity, &operations, false);
+    });
+}
+
+// check_dump() on every implementation, and dump_step() ends where a plain replay does
+#[cfg(feature = "dump")]
+#[test]
+fn test_lru_cache_dump() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in evicting_operations_strategy())| {
+        for implementation in q146_lru_cache::implementations() {
+            let mut cache = (implementation.new)(capacity as i32);
+            for operation in &operations {
+                match *operation {
+                    CacheOperation::Put { key, value } => cache.put(key, value),
+                    CacheOperation::Get { key } => {
+                        cache.get(key);
+                    }
+                }
+            }
+
+            let dump = cache.dump().expect("every registered cache has dump()");
+            check_dump(implementation.name, &*cache, &dump);
+
+            if let Some(last) = operations.len().checked_sub(1) {
+                let [_, after] = dump_step(implementation, capacity, &operations, last).unwrap();
+                assert_eq!(after.index.keys().collect::<Vec<_>>(), dump.index.keys().collect::<Vec<_>>());
+            }
+        }
+    });
+}
+
+// logs captured with RecordingCache, replayed through every implementation
+#[test]
+fn test_lru_cache_implementations_with_recordings() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/recordings");
+
+    for (path, recording) in load_recordings(dir).unwrap() {
+        let capacity = recording
+            .capacity
+            .unwrap_or_else(|| panic!("{:?} has no capacity", path));
+
+        test_lru_cache_with_operations(capacity, recording.cache_operations());
+
+        let mut cache = q146_lru_cache::two_hashmaps::LRUCache::new(capacity as i32);
+        if let Err(divergence) = check_recording(&mut cache, &recording) {
+            panic!("{:?}: {}", path, divergence);
+        }
+    }
+}
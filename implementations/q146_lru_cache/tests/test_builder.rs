@@ -0,0 +1,73 @@
+use proptest::prelude::*;
+
+use cache_util::*;
+use q146_lru_cache::vec_hashmap::{LRUCache, LRUEvictionCache};
+
+// (structure, storage) -> the registered implementation of the same type
+const BUILT: &[(&str, &str, &str)] = &[
+    ("priority_queue", "hashmap", "priority_queue"),
+    ("priority_queue", "policy", "priority_queue_eviction"),
+    ("vec_hashmap", "hashmap", "vec_hashmap"),
+    ("vec_hashmap", "policy", "vec_hashmap_eviction"),
+    ("d_ary_heap_4", "hashmap", "d_ary_heap_4"),
+    ("d_ary_heap_8", "policy", "d_ary_heap_8_eviction"),
+    ("pairing_heap", "hashmap", "pairing_heap"),
+    ("pairing_heap", "policy", "pairing_heap_eviction"),
+];
+
+// too slow under Miri, which only needs the implementations' own tests
+#[cfg_attr(miri, ignore)]
+#[test]
+fn test_built_lru_caches_match_registry() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in evicting_operations_strategy())| {
+        for &(structure, storage, name) in BUILT {
+            let implementation = q146_lru_cache::implementations()
+                .find(|implementation| implementation.name == name)
+                .unwrap();
+            let mut expected = (implementation.new)(capacity as i32);
+            let mut built = CacheBuilder::new("lru", capacity)
+                .structure(structure)
+                .storage(storage)
+                .build()
+                .unwrap();
+
+            for operation in &operations {
+                match *operation {
+                    CacheOperation::Put { key, value } => {
+                        expected.put(key, value);
+                        built.put(key, value);
+                    }
+                    CacheOperation::Get { key } => {
+                        let value = expected.get(key);
+                        prop_assert_eq!(
+                            built.get(&key),
+                            (value != -1).then_some(value),
+                            "{}/{} on get({})",
+                            structure,
+                            storage,
+                            key
+                        );
+                    }
+                }
+            }
+        }
+    });
+}
+
+// GenericCache and EvictionCache used to insert anyway when there was nothing to evict
+#[cfg_attr(miri, ignore)]
+#[test]
+fn test_generic_cache_zero_capacity() {
+    let mut cache = LRUCache::new(0);
+    cache.put(1, 1);
+    cache.put_many([(2, 2), (3, 3)]);
+    assert_eq!(cache.get(1), -1);
+    assert!(LeetCodeCache::is_empty(&cache));
+
+    let mut cache = LRUEvictionCache::new(0);
+    cache.put(1, 1);
+    cache.put_many([(2, 2), (3, 3)]);
+    assert_eq!(cache.get(1), -1);
+    assert!(LeetCodeCache::is_empty(&cache));
+}
@@ -0,0 +1,48 @@
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+use cache_util::*;
+
+// every key put() inserts either grows the cache or evicts one, on every implementation; too
+// slow under Miri, which only needs the implementations' own tests
+#[cfg_attr(miri, ignore)]
+#[test]
+fn test_lru_cache_metrics() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in evicting_operations_strategy())| {
+        for implementation in q146_lru_cache::implementations() {
+            let stats = CacheStats::default();
+            let mut cache = InstrumentedCache::new(
+                (implementation.new)(capacity as i32),
+                capacity,
+                stats.clone(),
+            );
+
+            let (mut gets, mut hits, mut inserted) = (0, 0, 0);
+            let mut seen = HashSet::new();
+            for operation in &operations {
+                match *operation {
+                    CacheOperation::Put { key, value } => {
+                        if !cache.contains(&key) {
+                            inserted += 1;
+                        }
+                        seen.insert(key);
+                        cache.put(key, value);
+                    }
+                    CacheOperation::Get { key } => {
+                        gets += 1;
+                        hits += cache.get(&key).is_some() as u64;
+                    }
+                }
+            }
+
+            let name = implementation.name;
+            prop_assert_eq!(stats.gets(), gets, "{} gets", name);
+            prop_assert_eq!(stats.hits(), hits, "{} hits", name);
+            prop_assert_eq!(stats.size(), cache.len() as u64, "{} size", name);
+            prop_assert_eq!(stats.capacity(), capacity as u64, "{} capacity", name);
+            prop_assert_eq!(inserted - stats.evictions(), cache.len() as u64, "{} evictions", name);
+            prop_assert_eq!(stats.evictions() == 0, seen.len() <= capacity, "{} evictions", name);
+        }
+    });
+}
@@ -126,22 +126,14 @@ impl Solution {
 
         let mut rst = usize::MAX;
 
-        // arr[p]
-        Self::search_peak(arr, p, v, size - 1, &mut rst);
-
-        // arr[v]
-        Self::search_valley(arr, v, 0, p, &mut rst);
-
-        // arr[p - 1]
-        if 0 < p {
-            p -= 1;
-            Self::search_peak(arr, p, v, size - 1, &mut rst);
+        // arr[0..=p]: keep the prefix up to each of them
+        for i in 0..=p {
+            Self::search_peak(arr, i, v, size - 1, &mut rst);
         }
 
-        // arr[v + 1]
-        if v < size - 1 {
-            v += 1;
-            Self::search_valley(arr, v, 0, p, &mut rst);
+        // arr[v..]: keep the suffix from each of them
+        for j in v..size {
+            Self::search_valley(arr, j, 0, p, &mut rst);
         }
 
         rst
@@ -6,6 +6,7 @@ use q1574_shortest_subarray_to_be_removed_to_make_array_sorted::impl_v3::Solutio
 
 #[rstest]
 #[case(vec![0, 16, 3, 13, 14, 11, 1, 24, 20, 20, 18, 15, 20], 10)]
+#[case(vec![26, 26, 26, 26, 26, 197, 197, 48, 1, 8, 26, 26, 26, 248], 5)]
 #[case(vec![2, 7, 4, 6, 11, 17, 4, 1, 5, 13, 10, 12], 8)]
 #[case(vec![6, 3, 10, 11, 15, 20, 13, 3, 18, 12], 8)]
 #[case(vec![1, 2, 3, 10, 0, 7, 8, 9], 2)]
@@ -0,0 +1,3 @@
+use cache_util::*;
+
+bench_implementations!(q460_lfu_cache);
@@ -0,0 +1,31 @@
+use cache_util::*;
+use criterion::{criterion_group, criterion_main};
+
+bench_cache!(
+    q460_lfu_cache_bench_d_ary_heap_4,
+    q460_lfu_cache::d_ary_heap::LFUCache<4>
+);
+
+bench_cache!(
+    q460_lfu_cache_bench_d_ary_heap_eviction_4,
+    q460_lfu_cache::d_ary_heap::LFUEvictionCache<4>
+);
+
+bench_cache!(
+    q460_lfu_cache_bench_d_ary_heap_8,
+    q460_lfu_cache::d_ary_heap::LFUCache<8>
+);
+
+bench_cache!(
+    q460_lfu_cache_bench_d_ary_heap_eviction_8,
+    q460_lfu_cache::d_ary_heap::LFUEvictionCache<8>
+);
+
+criterion_group!(
+    benches,
+    q460_lfu_cache_bench_d_ary_heap_4,
+    q460_lfu_cache_bench_d_ary_heap_eviction_4,
+    q460_lfu_cache_bench_d_ary_heap_8,
+    q460_lfu_cache_bench_d_ary_heap_eviction_8,
+);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+use cache_util::*;
+use criterion::{criterion_group, criterion_main};
+
+bench_cache!(
+    q460_lfu_cache_bench_freq_buckets,
+    q460_lfu_cache::freq_buckets::LFUCache
+);
+
+bench_cache!(
+    q460_lfu_cache_bench_freq_buckets_eviction,
+    q460_lfu_cache::freq_buckets::LFUEvictionCache
+);
+
+criterion_group!(
+    benches,
+    q460_lfu_cache_bench_freq_buckets,
+    q460_lfu_cache_bench_freq_buckets_eviction,
+);
+criterion_main!(benches);
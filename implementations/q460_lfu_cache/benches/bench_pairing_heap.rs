@@ -0,0 +1,19 @@
+use cache_util::*;
+use criterion::{criterion_group, criterion_main};
+
+bench_cache!(
+    q460_lfu_cache_bench_pairing_heap,
+    q460_lfu_cache::pairing_heap::LFUCache
+);
+
+bench_cache!(
+    q460_lfu_cache_bench_pairing_heap_eviction,
+    q460_lfu_cache::pairing_heap::LFUEvictionCache
+);
+
+criterion_group!(
+    benches,
+    q460_lfu_cache_bench_pairing_heap,
+    q460_lfu_cache_bench_pairing_heap_eviction,
+);
+criterion_main!(benches);
@@ -0,0 +1,3 @@
+use cache_util::*;
+
+define_benchmark!(q460_lfu_cache, unsafe_linked_list, LFUCache);
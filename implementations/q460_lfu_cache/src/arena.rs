@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+const NIL: u32 = u32::MAX; // null link
+
+#[derive(Debug)]
+struct Node {
+    key: i32,
+    val: i32,
+    freq: usize,
+
+    prev: u32,
+    next: u32, // also links the free list
+}
+
+// doubly-linked list of arena indices
+#[derive(Debug)]
+struct FreqList {
+    head: u32,
+    tail: u32,
+}
+
+impl FreqList {
+    pub fn new() -> Self {
+        FreqList {
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    pub fn push_back(&mut self, arena: &mut [Node], index: u32) {
+        arena[index as usize].prev = self.tail;
+        arena[index as usize].next = NIL;
+
+        match self.tail {
+            NIL => self.head = index,
+            tail => arena[tail as usize].next = index,
+        }
+
+        self.tail = index;
+    }
+
+    pub fn pop_front(&mut self, arena: &mut [Node]) -> Option<u32> {
+        match self.head {
+            NIL => None,
+            head => {
+                self.remove(arena, head);
+                Some(head)
+            }
+        }
+    }
+
+    pub fn remove(&mut self, arena: &mut [Node], index: u32) {
+        let (prev, next) = (arena[index as usize].prev, arena[index as usize].next);
+
+        match prev {
+            NIL => self.head = next,
+            prev => arena[prev as usize].next = next,
+        }
+
+        match next {
+            NIL => self.tail = prev,
+            next => arena[next as usize].prev = prev,
+        }
+
+        arena[index as usize].prev = NIL;
+        arena[index as usize].next = NIL;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head == NIL
+    }
+}
+
+pub struct LFUCache {
+    map: HashMap<i32, u32>,             // key -> node's index in arena
+    arena: Vec<Node>,                   // preallocated, nodes never move
+    free_head: u32,                     // head of the free list, threaded through Node::next
+    freq_map: HashMap<usize, FreqList>, // freq -> list of nodes, ordered by last access time
+    min_freq: usize,
+    capacity: usize, // as HashMap's capacity() could be auto-resized
+}
+
+/**
+ * `&self` means the method takes an immutable reference.
+ * If you need a mutable reference, change it to `&mut self` instead.
+ */
+impl LFUCache {
+    pub fn new(capacity: i32) -> Self {
+        Self {
+            map: HashMap::with_capacity(capacity as usize),
+            arena: Vec::with_capacity(capacity as usize),
+            free_head: NIL,
+            freq_map: HashMap::with_capacity(capacity as usize),
+            min_freq: 1,
+            capacity: capacity as usize,
+        }
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        match self.map.get(&key).copied() {
+            Some(index) => {
+                self.update(index);
+                self.arena[index as usize].val
+            }
+            _ => -1,
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        match self.map.get(&key).copied() {
+            Some(index) => {
+                self.arena[index as usize].val = value;
+                self.update(index);
+            }
+            _ => {
+                if self.map.len() == self.capacity {
+                    self.evict();
+                }
+
+                let index = self.alloc(key, value);
+                self.map.insert(key, index);
+                self.min_freq = 1;
+
+                self.freq_map
+                    .entry(1)
+                    .or_insert_with(FreqList::new)
+                    .push_back(&mut self.arena, index);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    fn alloc(&mut self, key: i32, val: i32) -> u32 {
+        let node = Node {
+            key,
+            val,
+            freq: 1,
+            prev: NIL,
+            next: NIL,
+        };
+
+        match self.free_head {
+            NIL => {
+                self.arena.push(node);
+                (self.arena.len() - 1) as u32
+            }
+            index => {
+                self.free_head = self.arena[index as usize].next;
+                self.arena[index as usize] = node;
+                index
+            }
+        }
+    }
+
+    fn release(&mut self, index: u32) {
+        self.arena[index as usize].next = self.free_head;
+        self.free_head = index;
+    }
+
+    fn evict(&mut self) {
+        let Some(freq_list) = self.freq_map.get_mut(&self.min_freq) else {
+            return;
+        };
+
+        if let Some(index) = freq_list.pop_front(&mut self.arena) {
+            self.map.remove(&self.arena[index as usize].key);
+
+            if freq_list.is_empty() {
+                self.freq_map.remove(&self.min_freq);
+            }
+
+            self.release(index);
+        }
+    }
+
+    fn update(&mut self, index: u32) {
+        let mut freq = self.arena[index as usize].freq;
+
+        if let Some(freq_list) = self.freq_map.get_mut(&freq) {
+            freq_list.remove(&mut self.arena, index);
+
+            if freq_list.is_empty() {
+                self.freq_map.remove(&freq);
+                if self.min_freq == freq {
+                    self.min_freq += 1;
+                }
+            }
+        }
+
+        freq += 1;
+        self.arena[index as usize].freq = freq;
+
+        self.freq_map
+            .entry(freq)
+            .or_insert_with(FreqList::new)
+            .push_back(&mut self.arena, index);
+    }
+}
+
+#[cfg(feature = "validate")]
+impl LFUCache {
+    // every list is non-empty with symmetric links and nodes of its own frequency, map
+    // points at the listed nodes, the rest are free, min_freq is the lowest frequency
+    pub fn debug_validate(&self) {
+        let mut listed = 0;
+        for (&freq, freq_list) in &self.freq_map {
+            assert_ne!(freq_list.head, NIL, "empty list for freq {}", freq);
+
+            let (mut prev, mut curr) = (NIL, freq_list.head);
+            while curr != NIL {
+                let node = &self.arena[curr as usize];
+
+                assert_eq!(node.prev, prev, "prev of {} doesn't match", curr);
+                assert_eq!(node.freq, freq, "{} is in the wrong list", node.key);
+                assert_eq!(
+                    self.map.get(&node.key),
+                    Some(&curr),
+                    "map[{}] isn't {}",
+                    node.key,
+                    curr
+                );
+
+                listed += 1;
+                (prev, curr) = (curr, node.next);
+            }
+
+            assert_eq!(freq_list.tail, prev, "tail isn't the last node");
+        }
+
+        let mut free = 0;
+        let mut curr = self.free_head;
+        while curr != NIL {
+            free += 1;
+            curr = self.arena[curr as usize].next;
+        }
+
+        assert_eq!(
+            listed + free,
+            self.arena.len(),
+            "leaked or double-freed nodes"
+        );
+
+        assert_eq!(listed, self.map.len(), "keys missing from the lists");
+        assert!(self.map.len() <= self.capacity, "over capacity");
+
+        if let Some(&lowest) = self.freq_map.keys().min() {
+            assert_eq!(self.min_freq, lowest, "min_freq isn't the lowest frequency");
+        }
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LFUCache {
+    // one cluster per frequency, node ids being arena indices; the walks are bounded by the
+    // capacity in case the links are broken
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("arena");
+
+        let mut freqs = self.freq_map.keys().copied().collect::<Vec<_>>();
+        freqs.sort_unstable();
+
+        for freq in freqs {
+            let list = std::iter::successors(
+                Some(self.freq_map[&freq].head).filter(|&head| head != NIL),
+                |&index| Some(self.arena[index as usize].next).filter(|&next| next != NIL),
+            )
+            .take(self.capacity);
+
+            dump.push_list(
+                Some(format!("freq {}", freq)),
+                list.map(|index| {
+                    let node = &self.arena[index as usize];
+                    (index as usize, node.key, format!("val {}", node.val))
+                }),
+            );
+        }
+
+        dump.index
+            .extend(self.map.iter().map(|(&key, &index)| (key, index as usize)));
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q460";
+    "arena" => LFUCache,
+);
+
+/*
+ * Your LFUCache object will be instantiated and called as such:
+ * let obj = LFUCache::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
@@ -0,0 +1,20 @@
+use crate::vec_hashmap::{DAryLFUCache, DAryLFUEvictionCache};
+
+// vec_hashmap with a wider heap: shallower, and a node's children share cache lines
+pub type LFUCache<const D: usize> = DAryLFUCache<D>;
+pub type LFUEvictionCache<const D: usize> = DAryLFUEvictionCache<D>;
+
+// the binary heap is vec_hashmap's
+cache_util::register_caches!("q460";
+    "d_ary_heap_4" => LFUCache<4>,
+    "d_ary_heap_4_eviction" => LFUEvictionCache<4>,
+    "d_ary_heap_8" => LFUCache<8>,
+    "d_ary_heap_8_eviction" => LFUEvictionCache<8>,
+);
+
+/*
+ * Your LFUCache object will be instantiated and called as such:
+ * let obj = LFUCache::<4>::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
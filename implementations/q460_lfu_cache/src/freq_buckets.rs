@@ -0,0 +1,70 @@
+use cache_util::EvictionPolicyFreqBuckets;
+use cache_util::HashMapStorage;
+use cache_util::{Cache, EvictionCache, GenericCache};
+
+pub struct LFUCache {
+    cache: GenericCache<EvictionPolicyFreqBuckets, HashMapStorage>,
+}
+
+pub struct LFUEvictionCache {
+    cache: EvictionCache<EvictionPolicyFreqBuckets<i32>>,
+}
+
+/**
+ * `&self` means the method takes an immutable reference.
+ * If you need a mutable reference, change it to `&mut self` instead.
+ */
+impl LFUCache {
+    pub fn new(capacity: i32) -> Self {
+        Self {
+            cache: GenericCache::new(
+                EvictionPolicyFreqBuckets::default(),
+                HashMapStorage::new(capacity as usize),
+                capacity as usize,
+            ),
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        self.cache.put(key, value);
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        self.cache.get(&key).unwrap_or(-1)
+    }
+
+    cache_util::forward_cache_methods!(cache);
+}
+
+impl LFUEvictionCache {
+    pub fn new(capacity: i32) -> Self {
+        Self {
+            cache: EvictionCache::new(
+                EvictionPolicyFreqBuckets::<i32>::default(),
+                capacity as usize,
+            ),
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        self.cache.put(key, value);
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        self.cache.get(&key).unwrap_or(-1)
+    }
+
+    cache_util::forward_cache_methods!(cache);
+}
+
+cache_util::register_caches!("q460";
+    "freq_buckets" => LFUCache,
+    "freq_buckets_eviction" => LFUEvictionCache,
+);
+
+/*
+ * Your LFUCache object will be instantiated and called as such:
+ * let obj = LFUCache::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
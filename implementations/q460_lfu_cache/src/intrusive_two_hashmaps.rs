@@ -71,15 +71,7 @@ impl LFUCache {
             self.update(node_rc);
         } else {
             if self.map.len() == self.capacity {
-                if let Some(freq_list) = self.freq_map.get_mut(&self.min_freq) {
-                    if let Some(node_rc) = freq_list.pop_front() {
-                        self.map.remove(&node_rc.key);
-
-                        if freq_list.is_empty() {
-                            self.freq_map.remove(&self.min_freq);
-                        }
-                    }
-                }
+                self.evict();
             }
 
             let node_rc = Rc::new(Node::new(key, value));
@@ -93,6 +85,18 @@ impl LFUCache {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
     /// Removes a node from its current frequency list.
     ///
     /// # Safety
@@ -128,6 +132,20 @@ impl LFUCache {
         }
     }
 
+    fn evict(&mut self) {
+        let Some(freq_list) = self.freq_map.get_mut(&self.min_freq) else {
+            return;
+        };
+
+        if let Some(node_rc) = freq_list.pop_front() {
+            self.map.remove(&node_rc.key);
+
+            if freq_list.is_empty() {
+                self.freq_map.remove(&self.min_freq);
+            }
+        }
+    }
+
     fn update(&mut self, node_rc: Rc<Node>) {
         self.remove_node_from_freq_list(&node_rc);
 
@@ -140,6 +158,80 @@ impl LFUCache {
     }
 }
 
+#[cfg(feature = "validate")]
+impl LFUCache {
+    // every list is non-empty, walks the same in both directions and holds nodes of its
+    // own frequency, every listed node is the one in map, min_freq is the lowest frequency
+    pub fn debug_validate(&self) {
+        let mut listed = 0;
+        for (&freq, freq_list) in &self.freq_map {
+            assert!(!freq_list.is_empty(), "empty list for freq {}", freq);
+
+            let mut count = 0;
+            for node in freq_list.iter() {
+                assert_eq!(node.freq.get(), freq, "{} is in the wrong list", node.key);
+                assert!(
+                    self.map
+                        .get(&node.key)
+                        .is_some_and(|n| std::ptr::eq(n.as_ref(), node)),
+                    "map[{}] isn't the listed node",
+                    node.key
+                );
+                count += 1;
+            }
+
+            assert_eq!(freq_list.iter().rev().count(), count, "asymmetric links");
+            listed += count;
+        }
+
+        assert_eq!(listed, self.map.len(), "keys missing from the lists");
+        assert!(self.map.len() <= self.capacity, "over capacity");
+
+        if let Some(&lowest) = self.freq_map.keys().min() {
+            assert_eq!(self.min_freq, lowest, "min_freq isn't the lowest frequency");
+        }
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LFUCache {
+    // one cluster per frequency, node ids in list order
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("intrusive_two_hashmaps");
+
+        let mut freqs = self.freq_map.keys().copied().collect::<Vec<_>>();
+        freqs.sort_unstable();
+
+        let mut listed = Vec::new();
+        for freq in freqs {
+            let start = listed.len();
+            listed.extend(self.freq_map[&freq].iter());
+
+            dump.push_list(
+                Some(format!("freq {}", freq)),
+                listed[start..].iter().enumerate().map(|(offset, node)| {
+                    (start + offset, node.key, format!("val {}", node.val.get()))
+                }),
+            );
+        }
+
+        for (&key, node_rc) in &self.map {
+            if let Some(id) = listed
+                .iter()
+                .position(|&n| std::ptr::eq(n, node_rc.as_ref()))
+            {
+                dump.index.insert(key, id);
+            }
+        }
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q460";
+    "intrusive_two_hashmaps" => LFUCache,
+);
+
 /*
  * Your LFUCache object will be instantiated and called as such:
  * let obj = LFUCache::new(capacity);
@@ -0,0 +1,78 @@
+use cache_util::HashMapStorage;
+use cache_util::{Cache, EvictionCache, GenericCache};
+use cache_util::{
+    EvictionPolicyPH, KeyAwareHeapNode, LFUHeapNode, LRUHeapNode, ValueAwareHeapNode,
+};
+
+pub struct LFUCache {
+    cache:
+        GenericCache<EvictionPolicyPH<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>, HashMapStorage>,
+}
+
+pub struct LFUEvictionCache {
+    cache: EvictionCache<
+        EvictionPolicyPH<ValueAwareHeapNode<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>>,
+    >,
+}
+
+/**
+ * `&self` means the method takes an immutable reference.
+ * If you need a mutable reference, change it to `&mut self` instead.
+ */
+impl LFUCache {
+    pub fn new(capacity: i32) -> Self {
+        Self {
+            cache: GenericCache::new(
+                EvictionPolicyPH::<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>::new(),
+                HashMapStorage::new(capacity as usize),
+                capacity as usize,
+            ),
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        self.cache.put(key, value);
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        self.cache.get(&key).unwrap_or(-1)
+    }
+
+    cache_util::forward_cache_methods!(cache);
+}
+
+impl LFUEvictionCache {
+    pub fn new(capacity: i32) -> Self {
+        Self {
+            cache:
+                EvictionCache::new(
+                    EvictionPolicyPH::<
+                        ValueAwareHeapNode<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>,
+                    >::new(),
+                    capacity as usize,
+                ),
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        self.cache.put(key, value);
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        self.cache.get(&key).unwrap_or(-1)
+    }
+
+    cache_util::forward_cache_methods!(cache);
+}
+
+cache_util::register_caches!("q460";
+    "pairing_heap" => LFUCache,
+    "pairing_heap_eviction" => LFUEvictionCache,
+);
+
+/*
+ * Your LFUCache object will be instantiated and called as such:
+ * let obj = LFUCache::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
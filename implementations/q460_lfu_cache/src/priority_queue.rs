@@ -32,6 +32,8 @@ impl LFUCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache);
 }
 
 impl LFUEvictionCache {
@@ -51,8 +53,15 @@ impl LFUEvictionCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache);
 }
 
+cache_util::register_caches!("q460";
+    "priority_queue" => LFUCache,
+    "priority_queue_eviction" => LFUEvictionCache,
+);
+
 /*
  * Your LFUCache object will be instantiated and called as such:
  * let obj = LFUCache::new(capacity);
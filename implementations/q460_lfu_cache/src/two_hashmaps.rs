@@ -151,15 +151,7 @@ impl LFUCache {
             }
             _ => {
                 if self.map.len() == self.capacity {
-                    if let Some(freq_list) = self.freq_map.get_mut(&self.min_freq) {
-                        if let Some(node_rc) = freq_list.pop_front() {
-                            self.map.remove(&node_rc.borrow().key);
-
-                            if freq_list.is_empty() {
-                                self.freq_map.remove(&self.min_freq);
-                            }
-                        }
-                    }
+                    self.evict();
                 }
 
                 let node_rc = Rc::new(RefCell::new(Node::new(key, value)));
@@ -172,6 +164,32 @@ impl LFUCache {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    fn evict(&mut self) {
+        let Some(freq_list) = self.freq_map.get_mut(&self.min_freq) else {
+            return;
+        };
+
+        if let Some(node_rc) = freq_list.pop_front() {
+            self.map.remove(&node_rc.borrow().key);
+
+            if freq_list.is_empty() {
+                self.freq_map.remove(&self.min_freq);
+            }
+        }
+    }
+
     fn update(&mut self, node_rc: Rc<RefCell<Node>>) {
         let mut freq = node_rc.borrow().freq;
 
@@ -195,6 +213,103 @@ impl LFUCache {
     }
 }
 
+#[cfg(feature = "validate")]
+impl LFUCache {
+    // every list is non-empty with symmetric links and nodes of its own frequency,
+    // every listed node is the one in map, min_freq is the lowest listed frequency
+    pub fn debug_validate(&self) {
+        let same = |a: &Option<Rc<RefCell<Node>>>, b: &Option<Rc<RefCell<Node>>>| match (a, b) {
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+
+        let mut listed = 0;
+        for (&freq, freq_list) in &self.freq_map {
+            assert!(!freq_list.is_empty(), "empty list for freq {}", freq);
+
+            let mut prev = None;
+            let mut curr = freq_list.head.clone();
+
+            while let Some(node_rc) = curr {
+                let node = node_rc.borrow();
+
+                let node_prev = node.prev.as_ref().and_then(Weak::upgrade);
+                assert!(
+                    same(&node_prev, &prev),
+                    "prev of {} doesn't match",
+                    node.key
+                );
+                assert_eq!(node.freq, freq, "{} is in the wrong list", node.key);
+                assert!(
+                    self.map
+                        .get(&node.key)
+                        .is_some_and(|n| Rc::ptr_eq(n, &node_rc)),
+                    "map[{}] isn't the listed node",
+                    node.key
+                );
+
+                listed += 1;
+                curr = node.next.clone();
+
+                drop(node);
+                prev = Some(node_rc);
+            }
+
+            assert!(same(&freq_list.tail, &prev), "tail isn't the last node");
+        }
+
+        assert_eq!(listed, self.map.len(), "keys missing from the lists");
+        assert!(self.map.len() <= self.capacity, "over capacity");
+
+        if let Some(&lowest) = self.freq_map.keys().min() {
+            assert_eq!(self.min_freq, lowest, "min_freq isn't the lowest frequency");
+        }
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LFUCache {
+    // one cluster per frequency, node ids in list order; the walks are bounded by the
+    // capacity in case the links are broken
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("two_hashmaps");
+
+        let mut freqs = self.freq_map.keys().copied().collect::<Vec<_>>();
+        freqs.sort_unstable();
+
+        let mut listed = Vec::new();
+        for freq in freqs {
+            let start = listed.len();
+            listed.extend(
+                std::iter::successors(self.freq_map[&freq].head.clone(), |node_rc| {
+                    node_rc.borrow().next.clone()
+                })
+                .take(self.capacity),
+            );
+
+            dump.push_list(
+                Some(format!("freq {}", freq)),
+                listed[start..].iter().enumerate().map(|(offset, node_rc)| {
+                    let node = node_rc.borrow();
+                    (start + offset, node.key, format!("val {}", node.val))
+                }),
+            );
+        }
+
+        for (&key, node_rc) in &self.map {
+            if let Some(id) = listed.iter().position(|n| Rc::ptr_eq(n, node_rc)) {
+                dump.index.insert(key, id);
+            }
+        }
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q460";
+    "two_hashmaps" => LFUCache,
+);
+
 /*
  * Your LFUCache object will be instantiated and called as such:
  * let obj = LFUCache::new(capacity);
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+use std::mem::MaybeUninit;
+use std::ptr::NonNull;
+
+type Link = Option<NonNull<Node>>;
+
+#[derive(Debug)]
+struct Node {
+    key: i32,
+    val: i32,
+    freq: usize,
+
+    prev: Link,
+    next: Link,
+}
+
+// doubly-linked list of raw pointers into the slab, no ownership
+#[derive(Debug)]
+struct FreqList {
+    head: Link,
+    tail: Link,
+}
+
+impl FreqList {
+    pub fn new() -> Self {
+        FreqList {
+            head: None,
+            tail: None,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `node` must point to an initialized slab node that is not linked in any list.
+    pub unsafe fn push_back(&mut self, mut node: NonNull<Node>) {
+        unsafe {
+            node.as_mut().prev = self.tail;
+            node.as_mut().next = None;
+
+            match self.tail {
+                Some(mut tail) => tail.as_mut().next = Some(node),
+                None => self.head = Some(node),
+            }
+        }
+
+        self.tail = Some(node);
+    }
+
+    /// # Safety
+    ///
+    /// All nodes linked in this list must be initialized slab nodes.
+    pub unsafe fn pop_front(&mut self) -> Link {
+        let head = self.head?;
+        unsafe { self.remove(head) };
+        Some(head)
+    }
+
+    /// # Safety
+    ///
+    /// `node` must point to an initialized slab node that is linked in this list.
+    pub unsafe fn remove(&mut self, mut node: NonNull<Node>) {
+        unsafe {
+            let (prev, next) = (node.as_ref().prev, node.as_ref().next);
+
+            match prev {
+                Some(mut prev) => prev.as_mut().next = next,
+                None => self.head = next,
+            }
+
+            match next {
+                Some(mut next) => next.as_mut().prev = prev,
+                None => self.tail = prev,
+            }
+
+            node.as_mut().prev = None;
+            node.as_mut().next = None;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+}
+
+pub struct LFUCache {
+    map: HashMap<i32, NonNull<Node>>,   // key -> node in slab
+    slab: NonNull<Node>,                // single owning allocation of `capacity` nodes
+    used: usize,                        // number of slab nodes initialized so far
+    freq_map: HashMap<usize, FreqList>, // freq -> list of nodes, ordered by last access time
+    min_freq: usize,
+    capacity: usize, // as HashMap's capacity() could be auto-resized
+}
+
+/**
+ * `&self` means the method takes an immutable reference.
+ * If you need a mutable reference, change it to `&mut self` instead.
+ */
+impl LFUCache {
+    pub fn new(capacity: i32) -> Self {
+        let slab = Box::<[Node]>::new_uninit_slice(capacity as usize);
+
+        Self {
+            map: HashMap::with_capacity(capacity as usize),
+            slab: NonNull::from(Box::leak(slab)).cast::<Node>(),
+            used: 0,
+            freq_map: HashMap::with_capacity(capacity as usize),
+            min_freq: 1,
+            capacity: capacity as usize,
+        }
+    }
+
+    pub fn get(&mut self, key: i32) -> i32 {
+        match self.map.get(&key).copied() {
+            // SAFETY: every node in map is initialized and linked in freq_map
+            Some(node) => unsafe {
+                self.update(node);
+                node.as_ref().val
+            },
+            _ => -1,
+        }
+    }
+
+    pub fn put(&mut self, key: i32, value: i32) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        match self.map.get(&key).copied() {
+            // SAFETY: every node in map is initialized and linked in freq_map
+            Some(mut node) => unsafe {
+                node.as_mut().val = value;
+                self.update(node);
+            },
+            _ => {
+                let node = if self.map.len() == self.capacity {
+                    // reuse the evicted node in place, the slab is already full
+                    // SAFETY: freq_map only links initialized nodes, and min_freq's list is non-empty here
+                    unsafe {
+                        let freq_list = self
+                            .freq_map
+                            .get_mut(&self.min_freq)
+                            .expect("min_freq list exists");
+                        let node = freq_list.pop_front().expect("cache is full");
+
+                        if freq_list.is_empty() {
+                            self.freq_map.remove(&self.min_freq);
+                        }
+
+                        self.map.remove(&node.as_ref().key);
+                        node
+                    }
+                } else {
+                    // SAFETY: used < capacity, so the slot is inside the slab
+                    let node = unsafe { self.slab.add(self.used) };
+                    self.used += 1;
+                    node
+                };
+
+                // SAFETY: `node` is inside the slab and no longer linked anywhere
+                unsafe {
+                    node.write(Node {
+                        key,
+                        val: value,
+                        freq: 1,
+                        prev: None,
+                        next: None,
+                    });
+                    self.freq_map
+                        .entry(1)
+                        .or_insert_with(FreqList::new)
+                        .push_back(node);
+                    self.map.insert(key, node);
+                }
+
+                self.min_freq = 1;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: i32) -> bool {
+        self.map.contains_key(&key)
+    }
+
+    /// # Safety
+    ///
+    /// `node` must be an initialized node linked in the list of its own `freq`.
+    unsafe fn update(&mut self, mut node: NonNull<Node>) {
+        unsafe {
+            let freq = node.as_ref().freq;
+
+            if let Some(freq_list) = self.freq_map.get_mut(&freq) {
+                freq_list.remove(node);
+
+                if freq_list.is_empty() {
+                    self.freq_map.remove(&freq);
+                    if self.min_freq == freq {
+                        self.min_freq += 1;
+                    }
+                }
+            }
+
+            node.as_mut().freq = freq + 1;
+
+            self.freq_map
+                .entry(freq + 1)
+                .or_insert_with(FreqList::new)
+                .push_back(node);
+        }
+    }
+}
+
+impl Drop for LFUCache {
+    fn drop(&mut self) {
+        // SAFETY: `slab` came from Box::leak() with exactly `capacity` elements,
+        // and Node needs no drop glue, so releasing the uninit slice is enough
+        unsafe {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                self.slab.as_ptr() as *mut MaybeUninit<Node>,
+                self.capacity,
+            )));
+        }
+    }
+}
+
+#[cfg(feature = "validate")]
+impl LFUCache {
+    // every list is non-empty with symmetric links and nodes of its own frequency, map
+    // points at the listed slab nodes, min_freq is the lowest frequency
+    pub fn debug_validate(&self) {
+        let slab = self.slab.as_ptr()..self.slab.as_ptr().wrapping_add(self.used);
+
+        let mut listed = 0;
+        for (&freq, freq_list) in &self.freq_map {
+            assert!(freq_list.head.is_some(), "empty list for freq {}", freq);
+
+            let (mut prev, mut curr) = (None, freq_list.head);
+            while let Some(node) = curr {
+                assert!(slab.contains(&node.as_ptr()), "node outside the slab");
+
+                // SAFETY: node is one of the `used` initialized slab nodes, checked above
+                let node_ref = unsafe { node.as_ref() };
+
+                assert_eq!(
+                    node_ref.prev, prev,
+                    "prev of {} doesn't match",
+                    node_ref.key
+                );
+                assert_eq!(node_ref.freq, freq, "{} is in the wrong list", node_ref.key);
+                assert_eq!(
+                    self.map.get(&node_ref.key),
+                    Some(&node),
+                    "map[{}] isn't the listed node",
+                    node_ref.key
+                );
+
+                listed += 1;
+                (prev, curr) = (Some(node), node_ref.next);
+            }
+
+            assert_eq!(freq_list.tail, prev, "tail isn't the last node");
+        }
+
+        assert!(self.used <= self.capacity, "slab overflow");
+
+        assert_eq!(listed, self.map.len(), "keys missing from the lists");
+        assert!(self.map.len() <= self.capacity, "over capacity");
+
+        if let Some(&lowest) = self.freq_map.keys().min() {
+            assert_eq!(self.min_freq, lowest, "min_freq isn't the lowest frequency");
+        }
+    }
+}
+
+#[cfg(feature = "dump")]
+impl LFUCache {
+    fn slab_index(&self, node: NonNull<Node>) -> usize {
+        (node.as_ptr() as usize - self.slab.as_ptr() as usize) / std::mem::size_of::<Node>()
+    }
+
+    // one cluster per frequency, node ids being slab indices; the walks stop at a node
+    // outside the slab, in case the links are broken
+    pub fn dump(&self) -> cache_util::StructureDump {
+        let mut dump = cache_util::StructureDump::new("unsafe_linked_list");
+        let slab = self.slab.as_ptr()..self.slab.as_ptr().wrapping_add(self.used);
+        let in_slab = |node: &NonNull<Node>| slab.contains(&node.as_ptr());
+
+        let mut freqs = self.freq_map.keys().copied().collect::<Vec<_>>();
+        freqs.sort_unstable();
+
+        for freq in freqs {
+            let listed = std::iter::successors(self.freq_map[&freq].head.filter(in_slab), |node| {
+                // SAFETY: in_slab() lets only initialized slab nodes through
+                unsafe { node.as_ref() }.next.filter(in_slab)
+            })
+            .take(self.capacity);
+
+            dump.push_list(
+                Some(format!("freq {}", freq)),
+                listed.map(|node| {
+                    // SAFETY: in_slab() lets only initialized slab nodes through
+                    let node_ref = unsafe { node.as_ref() };
+                    (
+                        self.slab_index(node),
+                        node_ref.key,
+                        format!("val {}", node_ref.val),
+                    )
+                }),
+            );
+        }
+
+        dump.index.extend(
+            self.map
+                .iter()
+                .map(|(&key, &node)| (key, self.slab_index(node))),
+        );
+
+        dump
+    }
+}
+
+cache_util::register_caches!("q460";
+    "unsafe_linked_list" => LFUCache,
+);
+
+/*
+ * Your LFUCache object will be instantiated and called as such:
+ * let obj = LFUCache::new(capacity);
+ * let ret_1: i32 = obj.get(key);
+ * obj.put(key, value);
+ */
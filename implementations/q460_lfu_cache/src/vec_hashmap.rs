@@ -1,29 +1,28 @@
 use cache_util::HashMapStorage;
 use cache_util::{Cache, EvictionCache, GenericCache};
-use cache_util::{
-    EvictionPolicyVHM, KeyAwareHeapNode, LFUHeapNode, LRUHeapNode, ValueAwareHeapNode,
-};
+use cache_util::{EvictionPolicyVHM, LFUHeapNode, LRUHeapNode, ValueAwareHeapNode};
 
-pub struct LFUCache {
-    cache:
-        GenericCache<EvictionPolicyVHM<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>, HashMapStorage>,
+// generic over the heap's arity, d_ary_heap having the 4-ary and 8-ary ones
+pub struct DAryLFUCache<const D: usize> {
+    cache: GenericCache<EvictionPolicyVHM<LFUHeapNode<LRUHeapNode>, D>, HashMapStorage>,
 }
 
-pub struct LFUEvictionCache {
-    cache: EvictionCache<
-        EvictionPolicyVHM<ValueAwareHeapNode<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>>,
-    >,
+pub struct DAryLFUEvictionCache<const D: usize> {
+    cache: EvictionCache<EvictionPolicyVHM<ValueAwareHeapNode<LFUHeapNode<LRUHeapNode>>, D>>,
 }
 
+pub type LFUCache = DAryLFUCache<2>;
+pub type LFUEvictionCache = DAryLFUEvictionCache<2>;
+
 /**
  * `&self` means the method takes an immutable reference.
  * If you need a mutable reference, change it to `&mut self` instead.
  */
-impl LFUCache {
+impl<const D: usize> DAryLFUCache<D> {
     pub fn new(capacity: i32) -> Self {
         Self {
             cache: GenericCache::new(
-                EvictionPolicyVHM::<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>::new(),
+                EvictionPolicyVHM::<LFUHeapNode<LRUHeapNode>, D>::new(),
                 HashMapStorage::new(capacity as usize),
                 capacity as usize,
             ),
@@ -37,18 +36,17 @@ impl LFUCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache, batch);
 }
 
-impl LFUEvictionCache {
+impl<const D: usize> DAryLFUEvictionCache<D> {
     pub fn new(capacity: i32) -> Self {
         Self {
-            cache:
-                EvictionCache::new(
-                    EvictionPolicyVHM::<
-                        ValueAwareHeapNode<KeyAwareHeapNode<LFUHeapNode<LRUHeapNode>>>,
-                    >::new(),
-                    capacity as usize,
-                ),
+            cache: EvictionCache::new(
+                EvictionPolicyVHM::<ValueAwareHeapNode<LFUHeapNode<LRUHeapNode>>, D>::new(),
+                capacity as usize,
+            ),
         }
     }
 
@@ -59,8 +57,15 @@ impl LFUEvictionCache {
     pub fn get(&mut self, key: i32) -> i32 {
         self.cache.get(&key).unwrap_or(-1)
     }
+
+    cache_util::forward_cache_methods!(cache, batch);
 }
 
+cache_util::register_caches!("q460";
+    "vec_hashmap" => LFUCache,
+    "vec_hashmap_eviction" => LFUEvictionCache,
+);
+
 /*
  * Your LFUCache object will be instantiated and called as such:
  * let obj = LFUCache::new(capacity);
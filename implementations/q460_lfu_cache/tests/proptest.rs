@@ -1,4 +1,5 @@
 use proptest::prelude::*;
+use std::panic::AssertUnwindSafe;
 
 // Define an enum to represent cache operations
 #[derive(Debug, Clone)]
@@ -29,69 +30,100 @@ fn operation_sequence_strategy() -> impl Strategy<Value = Vec<CacheOperation>> {
 }
 
 fn test_lfu_cache_with_operations(capacity: i32, operations: Vec<CacheOperation>) {
-    use q460_lfu_cache::intrusive_two_hashmaps::LFUCache as LFUCache_intrusive_two_hashmaps;
-    use q460_lfu_cache::priority_queue::LFUCache as LFUCache_priority_queue;
-    use q460_lfu_cache::priority_queue::LFUEvictionCache as LFUEvictionCache_priority_queue;
-    use q460_lfu_cache::two_hashmaps::LFUCache as LFUCache_two_hashmaps;
-    use q460_lfu_cache::vec_hashmap::LFUCache as LFUCache_vec_hashmap;
-    use q460_lfu_cache::vec_hashmap::LFUEvictionCache as LFUEvictionCache_vec_hashmap;
-
-    let mut cache_priority_queue = LFUCache_priority_queue::new(capacity);
-    let mut cache_priority_queue_eviction = LFUEvictionCache_priority_queue::new(capacity);
-    let mut cache_vec_hashmap = LFUCache_vec_hashmap::new(capacity);
-    let mut cache_vec_hashmap_eviction = LFUEvictionCache_vec_hashmap::new(capacity);
-    let mut cache_two_hashmaps = LFUCache_two_hashmaps::new(capacity);
-    let mut cache_intrusive_two_hashmaps = LFUCache_intrusive_two_hashmaps::new(capacity);
-
-    for operation in operations {
-        match operation {
+    let mut caches = q460_lfu_cache::implementations()
+        .map(|implementation| (implementation.name, (implementation.new)(capacity)))
+        .collect::<Vec<_>>();
+
+    for (step, operation) in operations.iter().enumerate() {
+        match *operation {
             CacheOperation::Put { key, value } => {
-                cache_priority_queue.put(key, value);
-                cache_priority_queue_eviction.put(key, value);
-                cache_vec_hashmap.put(key, value);
-                cache_vec_hashmap_eviction.put(key, value);
-                cache_two_hashmaps.put(key, value);
-                cache_intrusive_two_hashmaps.put(key, value);
+                for (_, cache) in caches.iter_mut() {
+                    cache.put(key, value);
+                }
             }
             CacheOperation::Get { key } => {
-                let result_priority_queue = cache_priority_queue.get(key);
-                let result_priority_queue_eviction = cache_priority_queue_eviction.get(key);
-                let result_vec_hashmap = cache_vec_hashmap.get(key);
-                let result_vec_hashmap_eviction = cache_vec_hashmap_eviction.get(key);
-                let result_two_hashmaps = cache_two_hashmaps.get(key);
-                let result_intrusive_two_hashmaps = cache_intrusive_two_hashmaps.get(key);
+                let results = caches
+                    .iter_mut()
+                    .map(|(name, cache)| (*name, cache.get(key)))
+                    .collect::<Vec<_>>();
 
                 // Compare results
-                assert_eq!(
-                    result_priority_queue, result_priority_queue_eviction,
-                    "priority_queue and priority_queue_eviction differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_vec_hashmap,
-                    "priority_queue and vec_hashmap differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_vec_hashmap_eviction,
-                    "priority_queue and vec_hashmap_eviction differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_two_hashmaps,
-                    "priority_queue and two_hashmaps differ on get({})",
-                    key
-                );
-                assert_eq!(
-                    result_priority_queue, result_intrusive_two_hashmaps,
-                    "priority_queue and intrusive_two_hashmaps differ on get({})",
-                    key
+                let (first, expected) = results[0];
+                for &(name, result) in &results[1..] {
+                    assert_eq!(
+                        expected,
+                        result,
+                        "{} and {} differ on get({}){}",
+                        first,
+                        name,
+                        key,
+                        dump_step_of(&[first, name], capacity, &operations, step)
+                    );
+                }
+            }
+        }
+
+        // structural invariants, after every operation (a no-op without the validate feature)
+        for (name, cache) in &caches {
+            let validated = std::panic::catch_unwind(AssertUnwindSafe(|| cache.debug_validate()));
+            if let Err(panic) = validated {
+                eprintln!(
+                    "{} broke at step {}{}",
+                    name,
+                    step,
+                    dump_step_of(&[name], capacity, &operations, step)
                 );
+                std::panic::resume_unwind(panic);
             }
         }
     }
 }
 
+// with the dump feature, writes the structures of `names` right before and after
+// operations[step] and says where
+#[cfg(feature = "dump")]
+fn dump_step_of(
+    names: &[&str],
+    capacity: i32,
+    operations: &[CacheOperation],
+    step: usize,
+) -> String {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("dumps");
+    let operations = to_cache_util(operations);
+
+    for implementation in q460_lfu_cache::implementations().filter(|i| names.contains(&i.name)) {
+        cache_util::write_step_dumps(&dir, implementation, capacity as usize, &operations, step)
+            .unwrap();
+    }
+
+    format!(", structures around it in {}", dir.display())
+}
+
+#[cfg(feature = "dump")]
+fn to_cache_util(operations: &[CacheOperation]) -> Vec<cache_util::CacheOperation> {
+    operations
+        .iter()
+        .map(|operation| match *operation {
+            CacheOperation::Put { key, value } => cache_util::CacheOperation::Put { key, value },
+            CacheOperation::Get { key } => cache_util::CacheOperation::Get { key },
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "dump"))]
+fn dump_step_of(_: &[&str], _: i32, _: &[CacheOperation], _: usize) -> String {
+    String::new()
+}
+
+// a module calling register_caches! but missing from implementations() would skip every test
+#[test]
+fn test_every_module_is_registered() {
+    let src = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+    let missing = cache_util::unregistered_modules(src, q460_lfu_cache::implementations()).unwrap();
+
+    assert!(missing.is_empty(), "{:?} not in implementations()", missing);
+}
+
 #[test]
 fn test_lfu_cache_implementations() {
     let config = ProptestConfig::with_cases(10); // Number of test cases to generate
@@ -99,3 +131,129 @@ fn test_lfu_cache_implementations() {
         test_lfu_cache_with_operations(capacity, operations);
     });
 }
+
+fn from_cache_util(operations: Vec<cache_util::CacheOperation>) -> Vec<CacheOperation> {
+    operations
+        .into_iter()
+        .map(|operation| match operation {
+            cache_util::CacheOperation::Put { key, value } => CacheOperation::Put { key, value },
+            cache_util::CacheOperation::Get { key } => CacheOperation::Get { key },
+        })
+        .collect()
+}
+
+#[test]
+fn test_lfu_cache_implementations_with_workloads() {
+    use cache_util::{NUM_PROPTEST_CASES, OperationsRangeProvider};
+    use cache_util::{mock_operations_range_provider_default, workload_operations_strategy};
+
+    let provider = mock_operations_range_provider_default();
+
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |(capacity in 1..=10_000i32, (_workload, operations) in workload_operations_strategy(provider.operations_range()))| {
+        test_lfu_cache_with_operations(capacity, from_cache_util(operations));
+    });
+}
+
+// the caches with a bulk path (a heap built once), by registered name
+const BULK_LOADED: &[(&str, cache_util::FromIter)] = cache_util::bulk_loaded![
+    "d_ary_heap_8" => q460_lfu_cache::d_ary_heap::LFUCache<8>,
+    "d_ary_heap_4_eviction" => q460_lfu_cache::d_ary_heap::LFUEvictionCache<4>,
+    "vec_hashmap" => q460_lfu_cache::vec_hashmap::LFUCache,
+    "vec_hashmap_eviction" => q460_lfu_cache::vec_hashmap::LFUEvictionCache,
+];
+
+#[test]
+fn test_lfu_cache_bulk_load() {
+    let config = ProptestConfig::with_cases(cache_util::NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, items, operations) in cache_util::bulk_load_strategy())| {
+        cache_util::check_bulk_load(q460_lfu_cache::implementations(), BULK_LOADED, capacity, &items, &operations);
+    });
+}
+
+#[test]
+fn test_lfu_cache_batches() {
+    use cache_util::test_batches_with;
+    use q460_lfu_cache::{d_ary_heap, vec_hashmap};
+
+    let config = ProptestConfig::with_cases(cache_util::NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, batches) in cache_util::batches_strategy())| {
+        test_batches_with!(d_ary_heap::LFUCache<8>, capacity, &batches);
+        test_batches_with!(d_ary_heap::LFUEvictionCache<4>, capacity, &batches);
+        test_batches_with!(vec_hashmap::LFUCache, capacity, &batches);
+        test_batches_with!(vec_hashmap::LFUEvictionCache, capacity, &batches);
+    });
+}
+
+// LFU nodes count their accesses, the put() that inserted the key included
+#[test]
+fn test_lfu_cache_introspection() {
+    use cache_util::test_introspection_with;
+    use q460_lfu_cache::{d_ary_heap, freq_buckets, pairing_heap, priority_queue, vec_hashmap};
+
+    let config = ProptestConfig::with_cases(cache_util::NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in cache_util::evicting_operations_strategy())| {
+        test_introspection_with!(d_ary_heap::LFUCache<8>, capacity, &operations, true);
+        test_introspection_with!(d_ary_heap::LFUEvictionCache<4>, capacity, &operations, true);
+        test_introspection_with!(freq_buckets::LFUCache, capacity, &operations, true);
+        test_introspection_with!(freq_buckets::LFUEvictionCache, capacity, &operations, true);
+        test_introspection_with!(pairing_heap::LFUCache, capacity, &operations, true);
+        test_introspection_with!(pairing_heap::LFUEvictionCache, capacity, &operations, true);
+        test_introspection_with!(priority_queue::LFUCache, capacity, &operations, true);
+        test_introspection_with!(priority_queue::LFUEvictionCache, capacity, &operations, true);
+        test_introspection_with!(vec_hashmap::LFUCache, capacity, &operations, true);
+        test_introspection_with!(vec_hashmap::LFUEvictionCache, capacity, &operations, true);
+    });
+}
+
+// check_dump() on every implementation, and dump_step() ends where a plain replay does
+#[cfg(feature = "dump")]
+#[test]
+fn test_lfu_cache_dump() {
+    use cache_util::CacheOperation;
+
+    let config = ProptestConfig::with_cases(cache_util::NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in cache_util::evicting_operations_strategy())| {
+        for implementation in q460_lfu_cache::implementations() {
+            let mut cache = (implementation.new)(capacity as i32);
+            for operation in &operations {
+                match *operation {
+                    CacheOperation::Put { key, value } => cache.put(key, value),
+                    CacheOperation::Get { key } => {
+                        cache.get(key);
+                    }
+                }
+            }
+
+            let dump = cache.dump().expect("every registered cache has dump()");
+            cache_util::check_dump(implementation.name, &*cache, &dump);
+
+            if let Some(last) = operations.len().checked_sub(1) {
+                let [_, after] = cache_util::dump_step(implementation, capacity, &operations, last).unwrap();
+                assert_eq!(after.index.keys().collect::<Vec<_>>(), dump.index.keys().collect::<Vec<_>>());
+            }
+        }
+    });
+}
+
+// logs captured with cache_util::RecordingCache, replayed through every implementation
+#[test]
+fn test_lfu_cache_implementations_with_recordings() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/recordings");
+
+    for (path, recording) in cache_util::load_recordings(dir).unwrap() {
+        let capacity = recording
+            .capacity
+            .unwrap_or_else(|| panic!("{:?} has no capacity", path));
+
+        test_lfu_cache_with_operations(
+            capacity as i32,
+            from_cache_util(recording.cache_operations()),
+        );
+
+        let mut cache = q460_lfu_cache::two_hashmaps::LFUCache::new(capacity as i32);
+        if let Err(divergence) = cache_util::check_recording(&mut cache, &recording) {
+            panic!("{:?}: {}", path, divergence);
+        }
+    }
+}
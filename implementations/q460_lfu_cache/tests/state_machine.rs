@@ -0,0 +1,115 @@
+use proptest::prelude::*;
+use proptest_state_machine::{ReferenceStateMachine, StateMachineTest, prop_state_machine};
+
+use cache_util::{Cache, LFUModel, LeetCodeCache, NUM_PROPTEST_CASES};
+
+#[derive(Debug, Clone)]
+enum Transition {
+    Put { key: i32, value: i32 },
+    Get { key: i32 },
+}
+
+#[derive(Debug, Clone)]
+struct ModelState {
+    model: LFUModel,
+    last_get: Option<i32>, // what the model returned for the latest Get
+}
+
+struct Reference;
+
+impl ReferenceStateMachine for Reference {
+    type State = ModelState;
+    type Transition = Transition;
+
+    fn init_state() -> BoxedStrategy<Self::State> {
+        (1..=8usize)
+            .prop_map(|capacity| ModelState {
+                model: LFUModel::new(capacity),
+                last_get: None,
+            })
+            .boxed()
+    }
+
+    // few keys compared to the capacity, so hits, updates and evictions all happen
+    fn transitions(state: &Self::State) -> BoxedStrategy<Self::Transition> {
+        let keys = 0..=(state.model.capacity() as i32 * 2);
+
+        prop_oneof![
+            (keys.clone(), 0..=1_000i32).prop_map(|(key, value)| Transition::Put { key, value }),
+            keys.prop_map(|key| Transition::Get { key }),
+        ]
+        .boxed()
+    }
+
+    fn apply(mut state: Self::State, transition: &Self::Transition) -> Self::State {
+        match *transition {
+            Transition::Put { key, value } => state.model.put(key, value),
+            Transition::Get { key } => state.last_get = state.model.get(&key),
+        }
+
+        state
+    }
+}
+
+// every registered implementation, side by side against the same model
+struct CacheTest;
+
+type Caches = Vec<(&'static str, Box<dyn LeetCodeCache>)>;
+
+impl StateMachineTest for CacheTest {
+    type SystemUnderTest = Caches;
+    type Reference = Reference;
+
+    fn init_test(ref_state: &ModelState) -> Caches {
+        q460_lfu_cache::implementations()
+            .map(|implementation| {
+                let capacity = ref_state.model.capacity() as i32;
+                (implementation.name, (implementation.new)(capacity))
+            })
+            .collect()
+    }
+
+    fn apply(mut caches: Caches, ref_state: &ModelState, transition: Transition) -> Caches {
+        for (name, cache) in caches.iter_mut() {
+            match transition {
+                Transition::Put { key, value } => cache.put(key, value),
+                Transition::Get { key } => {
+                    assert_eq!(
+                        cache.get(key),
+                        ref_state.last_get.unwrap_or(-1),
+                        "{}: get({}) differs from the model",
+                        name,
+                        key
+                    );
+                }
+            }
+        }
+
+        caches
+    }
+
+    fn check_invariants(caches: &Caches, ref_state: &ModelState) {
+        for (name, cache) in caches {
+            assert!(
+                cache.len() <= ref_state.model.capacity(),
+                "{} is over capacity",
+                name
+            );
+            assert_eq!(
+                cache.len(),
+                ref_state.model.len(),
+                "{} has the wrong len",
+                name
+            );
+
+            cache.debug_validate();
+        }
+    }
+}
+
+prop_state_machine! {
+    #![proptest_config(ProptestConfig::with_cases(NUM_PROPTEST_CASES))]
+
+    #[test]
+    fn test_all_implementations(sequential 1..200 => CacheTest);
+}
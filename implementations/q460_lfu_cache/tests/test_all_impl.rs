@@ -1,11 +1,6 @@
 use rstest::rstest;
 
-use q460_lfu_cache::intrusive_two_hashmaps::LFUCache as LFUCache_intrusive_two_hashmaps;
-use q460_lfu_cache::priority_queue::LFUCache as LFUCache_priority_queue;
-use q460_lfu_cache::priority_queue::LFUEvictionCache as LFUEvictionCache_priority_queue;
-use q460_lfu_cache::two_hashmaps::LFUCache as LFUCache_two_hashmaps;
-use q460_lfu_cache::vec_hashmap::LFUCache as LFUCache_vec_hashmap;
-use q460_lfu_cache::vec_hashmap::LFUEvictionCache as LFUEvictionCache_vec_hashmap;
+use q460_lfu_cache::implementations;
 
 #[rstest]
 #[case(vec!["LFUCache", "put", "put", "get", "put", "get", "get", "put", "get", "get", "get"],
@@ -27,12 +22,9 @@ fn test_all_impl(
     assert_eq!(cmds[0], "LFUCache");
     assert_eq!(expected_list[0], None);
 
-    let mut cache_priority_queue = LFUCache_priority_queue::new(args_list[0][0]);
-    let mut cache_priority_queue_eviction = LFUEvictionCache_priority_queue::new(args_list[0][0]);
-    let mut cache_vec_hashmap = LFUCache_vec_hashmap::new(args_list[0][0]);
-    let mut cache_vec_hashmap_eviction = LFUEvictionCache_vec_hashmap::new(args_list[0][0]);
-    let mut cache_two_hashmaps = LFUCache_two_hashmaps::new(args_list[0][0]);
-    let mut cache_intrusive_two_hashmaps = LFUCache_intrusive_two_hashmaps::new(args_list[0][0]);
+    let mut caches = implementations()
+        .map(|implementation| (implementation.name, (implementation.new)(args_list[0][0])))
+        .collect::<Vec<_>>();
 
     for (i, cmd) in cmds.iter().enumerate().skip(1) {
         let args = &args_list[i];
@@ -44,12 +36,9 @@ fn test_all_impl(
                     assert_eq!(args.len(), 1);
                     let key = args[0];
 
-                    assert_eq!(cache_priority_queue.get(key), v);
-                    assert_eq!(cache_priority_queue_eviction.get(key), v);
-                    assert_eq!(cache_vec_hashmap.get(key), v);
-                    assert_eq!(cache_vec_hashmap_eviction.get(key), v);
-                    assert_eq!(cache_two_hashmaps.get(key), v);
-                    assert_eq!(cache_intrusive_two_hashmaps.get(key), v);
+                    for (name, cache) in caches.iter_mut() {
+                        assert_eq!(cache.get(key), v, "{} on get({})", name, key);
+                    }
                 }
                 None => {
                     panic!("expected value should not be None for cmd \"get\"");
@@ -64,12 +53,9 @@ fn test_all_impl(
                     let key = args[0];
                     let value = args[1];
 
-                    cache_priority_queue.put(key, value);
-                    cache_priority_queue_eviction.put(key, value);
-                    cache_vec_hashmap.put(key, value);
-                    cache_vec_hashmap_eviction.put(key, value);
-                    cache_two_hashmaps.put(key, value);
-                    cache_intrusive_two_hashmaps.put(key, value);
+                    for (_, cache) in caches.iter_mut() {
+                        cache.put(key, value);
+                    }
                 }
             },
             _ => {
@@ -0,0 +1,57 @@
+use proptest::prelude::*;
+
+use cache_util::*;
+
+// (structure, storage) -> the registered implementation of the same type
+const BUILT: &[(&str, &str, &str)] = &[
+    ("priority_queue", "hashmap", "priority_queue"),
+    ("priority_queue", "policy", "priority_queue_eviction"),
+    ("vec_hashmap", "hashmap", "vec_hashmap"),
+    ("vec_hashmap", "policy", "vec_hashmap_eviction"),
+    ("d_ary_heap_4", "hashmap", "d_ary_heap_4"),
+    ("d_ary_heap_8", "policy", "d_ary_heap_8_eviction"),
+    ("pairing_heap", "hashmap", "pairing_heap"),
+    ("pairing_heap", "policy", "pairing_heap_eviction"),
+    ("freq_buckets", "hashmap", "freq_buckets"),
+    ("freq_buckets", "policy", "freq_buckets_eviction"),
+];
+
+// too slow under Miri, which only needs the implementations' own tests
+#[cfg_attr(miri, ignore)]
+#[test]
+fn test_built_lfu_caches_match_registry() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in evicting_operations_strategy())| {
+        for &(structure, storage, name) in BUILT {
+            let implementation = q460_lfu_cache::implementations()
+                .find(|implementation| implementation.name == name)
+                .unwrap();
+            let mut expected = (implementation.new)(capacity as i32);
+            let mut built = CacheBuilder::new("lfu", capacity)
+                .structure(structure)
+                .storage(storage)
+                .build()
+                .unwrap();
+
+            for operation in &operations {
+                match *operation {
+                    CacheOperation::Put { key, value } => {
+                        expected.put(key, value);
+                        built.put(key, value);
+                    }
+                    CacheOperation::Get { key } => {
+                        let value = expected.get(key);
+                        prop_assert_eq!(
+                            built.get(&key),
+                            (value != -1).then_some(value),
+                            "{}/{} on get({})",
+                            structure,
+                            storage,
+                            key
+                        );
+                    }
+                }
+            }
+        }
+    });
+}
@@ -16,7 +16,17 @@ fn main() -> anyhow::Result<()> {
             metrics_config,
             sub_crate,
             bench,
-        } => db::update_db(&metrics_config, &sub_crate, &bench),
+            criterion_dir,
+            latency_dir,
+            memory_dir,
+        } => db::update_db(
+            &metrics_config,
+            &sub_crate,
+            &bench,
+            &criterion_dir,
+            &latency_dir,
+            &memory_dir,
+        ),
         Command::UpdateDashboardTimeRange => dashboard::update_dashboard_time_range(),
     }
 }
@@ -16,6 +16,18 @@ pub enum Command {
         /// e.g., `bench_IMPL`
         #[arg(long, value_name = "BENCH_NAME")]
         bench: String,
+
+        /// Where criterion wrote its results, one directory per `BenchmarkId`.
+        #[arg(long, value_name = "CRITERION_DIR", default_value = "target/criterion")]
+        criterion_dir: String,
+
+        /// Where `bench_latency` benches write their per-operation percentiles.
+        #[arg(long, value_name = "LATENCY_DIR", default_value = "target/latency")]
+        latency_dir: String,
+
+        /// Where `bench_memory` benches write their per-implementation allocation counts.
+        #[arg(long, value_name = "MEMORY_DIR", default_value = "target/memory")]
+        memory_dir: String,
     },
     /// Update the dashboard's time range.
     UpdateDashboardTimeRange,
@@ -1,5 +1,8 @@
 pub const INFLUXDB_BUCKET: &str = "leetcode-rs_bench";
 pub const MEASUREMENT_NAME: &str = "bench_20241213"; // earliest meaningful date
+pub const PARAMETERIZED_MEASUREMENT_NAME: &str = "bench_matrix_20261019"; // one point per BenchmarkId
+pub const LATENCY_MEASUREMENT_NAME: &str = "latency_20261019"; // one point per operation kind
+pub const MEMORY_MEASUREMENT_NAME: &str = "memory_20261019"; // one point per implementation
 pub const COMMIT_RANGE: &str = "commit_range";
 
 pub const MAX_RETRIES: usize = 5;
@@ -0,0 +1,125 @@
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// The subset of criterion's `new/benchmark.json` needed to tag a result
+#[derive(Deserialize, Debug)]
+struct BenchmarkId {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+    /// `{"Elements": n}`, `{"Bytes": n}` or absent
+    throughput: Option<Value>,
+}
+
+/// One `BenchmarkId` of a parameterized group, e.g.
+/// `q146_lru_cache_bench_all/arena/workload=uniform,capacity=100,operations=10000`
+#[derive(Debug)]
+pub struct ParameterResult {
+    pub implementation: String,
+    /// `key=value` pairs of the parameter, in order
+    pub parameters: Vec<(String, String)>,
+    /// mean time of one iteration, in nanoseconds
+    pub runtime: f64,
+    pub ops_per_sec: Option<f64>,
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> anyhow::Result<T> {
+    let file = File::open(path).with_context(|| format!("Failed to open '{}'", path.display()))?;
+
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Failed to parse '{}' as JSON", path.display()))
+}
+
+/// "workload=zipfian:0.99,capacity=100,operations=10000" -> [("workload", "zipfian:0.99"), ...]
+/// a parameter without `=` is kept under the key "parameter"
+fn split_parameters(value_str: &str) -> Vec<(String, String)> {
+    value_str
+        .split(',')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => ("parameter".to_string(), pair.to_string()),
+        })
+        .collect()
+}
+
+fn parameter_result(benchmark_json: &Path, bench: &str) -> anyhow::Result<ParameterResult> {
+    let id: BenchmarkId = read_json(benchmark_json)?;
+
+    let estimates_json = benchmark_json.with_file_name("estimates.json");
+    let estimates: Value = read_json(&estimates_json)?;
+    let runtime = estimates
+        .pointer("/mean/point_estimate")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| {
+            anyhow!(
+                "Required field 'mean.point_estimate' not found in '{}'",
+                estimates_json.display()
+            )
+        })?;
+
+    let elements = id
+        .throughput
+        .as_ref()
+        .and_then(|throughput| throughput.get("Elements"))
+        .and_then(Value::as_f64);
+    let ops_per_sec = elements
+        .filter(|_| runtime > 0.0)
+        .map(|elements| elements / (runtime * 1e-9));
+
+    Ok(ParameterResult {
+        // bench_all groups every implementation, one function_id each
+        implementation: id
+            .function_id
+            .unwrap_or_else(|| bench.trim_start_matches("bench_").to_string()),
+        parameters: id
+            .value_str
+            .as_deref()
+            .map(split_parameters)
+            .unwrap_or_default(),
+        runtime,
+        ops_per_sec,
+    })
+}
+
+/// Every `new/benchmark.json` under `criterion_dir` whose group is `{sub_crate}_{bench}`,
+/// sorted by implementation and parameters; an unparameterized one is left to the
+/// `Runtime_estimate` of metrics_template.json
+pub fn collect_parameter_results(
+    criterion_dir: &str,
+    sub_crate: &str,
+    bench: &str,
+) -> anyhow::Result<Vec<ParameterResult>> {
+    let group = format!("{sub_crate}_{bench}");
+    let mut results = Vec::new();
+
+    for entry in WalkDir::new(criterion_dir) {
+        let entry = entry.with_context(|| format!("Failed to walk '{}'", criterion_dir))?;
+        let path = entry.path();
+
+        let in_new = path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|dir| dir == "new");
+        if !in_new || entry.file_name() != "benchmark.json" {
+            continue;
+        }
+
+        let id: BenchmarkId = read_json(path)?;
+        if id.group_id != group || id.value_str.is_none() {
+            continue;
+        }
+
+        results.push(parameter_result(path, bench)?);
+    }
+
+    results.sort_by(|a, b| {
+        (&a.implementation, &a.parameters).cmp(&(&b.implementation, &b.parameters))
+    });
+
+    Ok(results)
+}
@@ -1,4 +1,10 @@
-use super::constants::MEASUREMENT_NAME;
+use super::constants::{
+    LATENCY_MEASUREMENT_NAME, MEASUREMENT_NAME, MEMORY_MEASUREMENT_NAME,
+    PARAMETERIZED_MEASUREMENT_NAME,
+};
+use super::criterion::{ParameterResult, collect_parameter_results};
+use super::latency::{LatencyReport, collect_latency_reports};
+use super::memory::{MemoryReport, collect_memory_reports};
 use anyhow::{Context, Ok, anyhow};
 use reqwest::blocking::Client;
 use serde_json::{Map, Value};
@@ -6,6 +12,7 @@ use std::env;
 use std::fmt::Write;
 use std::fs::File;
 use std::io::BufReader;
+use std::path::Path;
 
 /// Dynamically extracts fields from a JSON `Value` using a dot-delimited path (e.g. "slope.`confidence_interval`.`lower_bound`")
 fn get_nested_value<'a>(value: &'a Value, field_path: &str) -> Option<&'a Value> {
@@ -58,12 +65,10 @@ fn collect_metrics_from_single_json(
                 }
             };
 
-            let mut rename_str = field_path;
-            if let Value::String(s) = rename {
-                if !s.is_empty() {
-                    rename_str = s;
-                }
-            }
+            let rename_str = match rename {
+                Value::String(s) if !s.is_empty() => s,
+                _ => field_path,
+            };
 
             if !metrics.is_empty() {
                 write!(&mut metrics, ",").with_context(|| {
@@ -120,6 +125,16 @@ fn collect_metrics(metrics_config: &str) -> anyhow::Result<String> {
             .and_then(|f| f.as_object())
             .ok_or_else(|| anyhow!("Each config pair must have a 'filters' object"))?;
 
+        // e.g. criterion's estimates, only written by the benches that time one function
+        let optional = pair
+            .get("optional")
+            .and_then(|o| o.as_bool())
+            .unwrap_or(false);
+        if optional && !Path::new(path).exists() {
+            println!("skipping optional metrics file '{}'", path);
+            continue;
+        }
+
         let sub_metrics = collect_metrics_from_single_json(path, filters)?;
 
         if !sub_metrics.is_empty() {
@@ -135,14 +150,101 @@ fn collect_metrics(metrics_config: &str) -> anyhow::Result<String> {
     Ok(metrics)
 }
 
-pub fn update_db(metrics_config: &str, sub_crate: &str, bench: &str) -> anyhow::Result<()> {
+/// Escapes commas, spaces and equals signs in an InfluxDB tag key or value
+fn escape_tag(tag: &str) -> String {
+    let mut escaped = String::with_capacity(tag.len());
+    for c in tag.chars() {
+        if matches!(c, ',' | ' ' | '=') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// One line per `BenchmarkId`, tagged with its implementation and each `key=value` of its parameter
+fn parameter_line(sub_crate: &str, result: &ParameterResult, timestamp: i64) -> String {
+    let mut tags = format!(
+        "qname={},impl={}",
+        escape_tag(sub_crate),
+        escape_tag(&result.implementation)
+    );
+    for (key, value) in &result.parameters {
+        write!(&mut tags, ",{}={}", escape_tag(key), escape_tag(value))
+            .expect("writing to a String never fails");
+    }
+
+    let mut fields = format!("runtime={}", result.runtime);
+    if let Some(ops_per_sec) = result.ops_per_sec {
+        write!(&mut fields, ",ops_per_sec={}", ops_per_sec)
+            .expect("writing to a String never fails");
+    }
+
+    format!("{PARAMETERIZED_MEASUREMENT_NAME},{tags} {fields} {timestamp}")
+}
+
+/// One line per operation kind (get, put, all) of a latency report
+fn latency_lines(sub_crate: &str, report: &LatencyReport, timestamp: i64) -> Vec<String> {
+    let tags = format!(
+        "qname={},impl={},workload={},capacity={},operations={}",
+        escape_tag(sub_crate),
+        escape_tag(&report.implementation),
+        escape_tag(&report.workload),
+        report.capacity,
+        report.operations
+    );
+
+    report
+        .summaries()
+        .into_iter()
+        .map(|(op, summary)| {
+            format!(
+                "{LATENCY_MEASUREMENT_NAME},{tags},op={op} count={},mean={},p50={},p99={},p999={},max={} {timestamp}",
+                summary.count, summary.mean, summary.p50, summary.p99, summary.p999, summary.max
+            )
+        })
+        .collect()
+}
+
+/// One line per implementation of a memory report, tagged like the other two
+fn memory_line(
+    sub_crate: &str,
+    implementation: &str,
+    report: &MemoryReport,
+    timestamp: i64,
+) -> String {
+    format!(
+        "{MEMORY_MEASUREMENT_NAME},qname={},impl={} allocations_per_op={},bytes_allocated_per_op={},bytes_allocated={},bytes_per_entry={} {timestamp}",
+        escape_tag(sub_crate),
+        escape_tag(implementation),
+        report.allocations_per_op,
+        report.bytes_allocated_per_op,
+        report.bytes_allocated,
+        report.bytes_per_entry
+    )
+}
+
+pub fn update_db(
+    metrics_config: &str,
+    sub_crate: &str,
+    bench: &str,
+    criterion_dir: &str,
+    latency_dir: &str,
+    memory_dir: &str,
+) -> anyhow::Result<()> {
     let influxdb_url = env::var("INFLUXDB_URL").with_context(|| "INFLUXDB_URL not set")?;
     let influxdb_token = env::var("INFLUXDB_TOKEN").with_context(|| "INFLUXDB_TOKEN not set")?;
     let influxdb_org = env::var("INFLUXDB_ORG").with_context(|| "INFLUXDB_ORG not set")?;
     let influxdb_bucket = env::var("INFLUXDB_BUCKET").with_context(|| "INFLUXDB_BUCKET not set")?;
 
     let metrics = collect_metrics(metrics_config)?;
-    if metrics.is_empty() {
+    let results = collect_parameter_results(criterion_dir, sub_crate, bench)?;
+    println!("parameterized results: {}", results.len());
+    let reports = collect_latency_reports(latency_dir, sub_crate, bench)?;
+    println!("latency reports: {}", reports.len());
+    let memory = collect_memory_reports(memory_dir, sub_crate, bench)?;
+    println!("memory reports: {}", memory.len());
+    if metrics.is_empty() && results.is_empty() && reports.is_empty() && memory.is_empty() {
         return Ok(());
     }
 
@@ -153,9 +255,29 @@ pub fn update_db(metrics_config: &str, sub_crate: &str, bench: &str) -> anyhow::
     const SINGLE_SPACE: char = ' ';
     // Prepare the InfluxDB line protocol data
     // Include all relevant statistics
-    let line = format!(
-        "{MEASUREMENT_NAME},qname={sub_crate},impl={}{SINGLE_SPACE}{metrics}{SINGLE_SPACE}{timestamp}",
-        bench.trim_start_matches("bench_"),
+    // Process-wide metrics (max RSS, CPU) of the whole bench, then one line per BenchmarkId,
+    // three per latency report and one per memory report
+    let mut lines = Vec::with_capacity(1 + results.len() + 3 * reports.len() + memory.len());
+    if !metrics.is_empty() {
+        lines.push(format!(
+            "{MEASUREMENT_NAME},qname={sub_crate},impl={}{SINGLE_SPACE}{metrics}{SINGLE_SPACE}{timestamp}",
+            bench.trim_start_matches("bench_"),
+        ));
+    }
+    lines.extend(
+        results
+            .iter()
+            .map(|result| parameter_line(sub_crate, result, timestamp)),
+    );
+    lines.extend(
+        reports
+            .iter()
+            .flat_map(|report| latency_lines(sub_crate, report, timestamp)),
+    );
+    lines.extend(
+        memory.iter().map(|(implementation, report)| {
+            memory_line(sub_crate, implementation, report, timestamp)
+        }),
     );
 
     // Send the data to InfluxDB
@@ -170,7 +292,7 @@ pub fn update_db(metrics_config: &str, sub_crate: &str, bench: &str) -> anyhow::
         .post(url)
         .header("Authorization", format!("Token {}", influxdb_token))
         .header("Content-Type", "text/plain; charset=utf-8")
-        .body(line)
+        .body(lines.join("\n"))
         .send()?;
 
     if response.status().is_success() {
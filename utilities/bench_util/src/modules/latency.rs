@@ -0,0 +1,52 @@
+use anyhow::Context;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Percentiles of one operation kind, in nanoseconds
+#[derive(Deserialize, Debug)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub mean: f64,
+    pub p50: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+/// One entry of the array `cache_util::bench_latency!` writes
+#[derive(Deserialize, Debug)]
+pub struct LatencyReport {
+    pub implementation: String,
+    pub workload: String,
+    pub capacity: usize,
+    pub operations: usize,
+    pub get: LatencySummary,
+    pub put: LatencySummary,
+    pub all: LatencySummary,
+}
+
+impl LatencyReport {
+    /// (op tag, summary) for each operation kind
+    pub fn summaries(&self) -> [(&'static str, &LatencySummary); 3] {
+        [("get", &self.get), ("put", &self.put), ("all", &self.all)]
+    }
+}
+
+/// `{latency_dir}/{sub_crate}_{bench}.json` if the bench wrote one, nothing otherwise
+pub fn collect_latency_reports(
+    latency_dir: &str,
+    sub_crate: &str,
+    bench: &str,
+) -> anyhow::Result<Vec<LatencyReport>> {
+    let path = Path::new(latency_dir).join(format!("{sub_crate}_{bench}.json"));
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = File::open(&path).with_context(|| format!("Failed to open '{}'", path.display()))?;
+
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Failed to parse '{}' as latency reports", path.display()))
+}
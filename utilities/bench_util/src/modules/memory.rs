@@ -0,0 +1,32 @@
+use anyhow::Context;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// One value of the object `cache_util::bench_memory!` writes, keyed by implementation
+#[derive(Deserialize, Debug)]
+pub struct MemoryReport {
+    pub allocations_per_op: f64,
+    pub bytes_allocated_per_op: f64,
+    pub bytes_allocated: u64,
+    pub bytes_per_entry: f64,
+}
+
+/// `{memory_dir}/{sub_crate}_{bench}.json` if the bench wrote one, nothing otherwise
+pub fn collect_memory_reports(
+    memory_dir: &str,
+    sub_crate: &str,
+    bench: &str,
+) -> anyhow::Result<BTreeMap<String, MemoryReport>> {
+    let path = Path::new(memory_dir).join(format!("{sub_crate}_{bench}.json"));
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+
+    let file = File::open(&path).with_context(|| format!("Failed to open '{}'", path.display()))?;
+
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Failed to parse '{}' as memory reports", path.display()))
+}
@@ -1,4 +1,7 @@
 pub mod args;
 pub mod constants;
+pub mod criterion;
 pub mod dashboard;
 pub mod db;
+pub mod latency;
+pub mod memory;
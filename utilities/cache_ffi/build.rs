@@ -0,0 +1,24 @@
+use std::env;
+use std::path::PathBuf;
+
+// cache_ffi.h from the `extern "C"` items of src/lib.rs
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml should be valid");
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("src/lib.rs should be exportable to C")
+        .write_to_file(out_dir.join("cache_ffi.h"));
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    // the C test program can't link against a library built with -Z sanitizer
+    println!("cargo::rustc-check-cfg=cfg(cache_ffi_sanitized)");
+    let rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+    if rustflags.contains("sanitizer=") {
+        println!("cargo::rustc-cfg=cache_ffi_sanitized");
+    }
+}
@@ -0,0 +1,118 @@
+#ifndef CACHE_FFI_H
+#define CACHE_FFI_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// An opaque cache, see cache_new().
+typedef struct CacheHandle CacheHandle;
+
+// What cache_stats() returns: counters since creation or cache_stats_reset(), and gauges.
+typedef struct CacheStatsSnapshot {
+  uint64_t hits;
+  uint64_t misses;
+  uint64_t puts;
+  uint64_t evictions;
+  uint64_t load_failures;
+  uint64_t size;
+  uint64_t capacity;
+} CacheStatsSnapshot;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a cache of `capacity` keys evicting by `policy` ("lru" or "lfu"), its order kept by
+// `structure` ("priority_queue", "vec_hashmap", "d_ary_heap_{2,4,8}", "pairing_heap" or, for
+// "lfu", "freq_buckets"; NULL for "vec_hashmap").
+//
+// Returns NULL if an argument is invalid, cache_last_error() saying which.
+//
+// # Safety
+//
+// `policy` and `structure` are NULL or NUL-terminated strings.
+struct CacheHandle *cache_new(const char *policy, const char *structure, size_t capacity);
+
+// Creates a cache from a CacheConfig in JSON (starting with '{') or TOML, e.g.
+// `policy = "lfu"`, `capacity = 1024`, `storage = "policy"`, `ttl_ms = 60000`.
+//
+// Returns NULL if the config is invalid, cache_last_error() saying why.
+//
+// # Safety
+//
+// `config` is NULL or a NUL-terminated string.
+struct CacheHandle *cache_new_from_config(const char *config);
+
+// Releases a cache; NULL is ignored.
+//
+// # Safety
+//
+// `cache` is NULL or a handle from cache_new*() not freed yet, and is not used afterwards.
+void cache_free(struct CacheHandle *cache);
+
+// Inserts or updates `key`, evicting a key if the cache is full.
+//
+// # Safety
+//
+// `cache` is NULL (ignored) or a live handle, used by no other thread meanwhile.
+void cache_put(struct CacheHandle *cache, int32_t key, int32_t value);
+
+// Looks `key` up, counting as an access. Returns whether it was found, its value then
+// written to `value` unless that is NULL.
+//
+// # Safety
+//
+// `cache` is NULL (a miss) or a live handle, used by no other thread meanwhile; `value` is
+// NULL or points to a writable int32_t.
+bool cache_get(struct CacheHandle *cache, int32_t key, int32_t *value);
+
+// Drops `key` as if it had never been put. Returns whether it was there, its value then
+// written to `value` unless that is NULL.
+//
+// # Safety
+//
+// As for cache_get().
+bool cache_remove(struct CacheHandle *cache, int32_t key, int32_t *value);
+
+// How many keys the cache holds, expired ones included until evicted.
+//
+// # Safety
+//
+// `cache` is NULL (0) or a live handle.
+size_t cache_len(const struct CacheHandle *cache);
+
+// Whether the cache holds `key`, without counting as an access.
+//
+// # Safety
+//
+// `cache` is NULL (false) or a live handle.
+bool cache_contains(const struct CacheHandle *cache, int32_t key);
+
+// The cache's statistics, all zero for NULL.
+//
+// # Safety
+//
+// `cache` is NULL or a live handle.
+struct CacheStatsSnapshot cache_stats(const struct CacheHandle *cache);
+
+// Zeroes the counters of cache_stats(), not the size and capacity.
+//
+// # Safety
+//
+// `cache` is NULL (ignored) or a live handle.
+void cache_stats_reset(struct CacheHandle *cache);
+
+// Why the last cache_new*() on this thread returned NULL, NULL if none did. Valid until the
+// next failing cache_new*() on the same thread.
+const char *cache_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CACHE_FFI_H */
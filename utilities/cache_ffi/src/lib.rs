@@ -0,0 +1,303 @@
+// C bindings over CacheBuilder, for C code to reuse the policies benchmarked here.
+//
+// A cache is an opaque CacheHandle from cache_new() or cache_new_from_config(), released by
+// cache_free(). A handle is not thread safe: one thread at a time, as with any C struct.
+// Every handle counts its own statistics. A panic never unwinds into C: the constructors
+// fail with it, and put, get and remove act as a miss. The `///` comments end up in
+// include/cache_ffi.h.
+
+use cache_util::{CacheBuilder, CacheConfig, CacheStats, RemovableCache};
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// An opaque cache, see cache_new().
+pub struct CacheHandle {
+    cache: Box<dyn RemovableCache>,
+    stats: CacheStats,
+}
+
+/// What cache_stats() returns: counters since creation or cache_stats_reset(), and gauges.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStatsSnapshot {
+    pub hits: u64,
+    pub misses: u64,
+    pub puts: u64,
+    pub evictions: u64,
+    pub load_failures: u64,
+    pub size: u64,
+    pub capacity: u64,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail(err: String) -> *mut CacheHandle {
+    // a message can't hold a NUL for C, so cut it there
+    let err = err.split('\0').next().unwrap_or_default().to_string();
+    LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(err).ok());
+
+    ptr::null_mut()
+}
+
+// `f()`, or `on_panic` with what it panicked with
+fn guard<T>(f: impl FnOnce() -> T, on_panic: impl FnOnce(String) -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| on_panic(message(&*panic)))
+}
+
+fn message(panic: &(dyn Any + Send)) -> String {
+    match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        (None, None) => "unknown panic".to_string(),
+    }
+}
+
+// None for NULL
+//
+// SAFETY: `text` is NULL or a NUL-terminated string that outlives 'a
+unsafe fn text_arg<'a>(text: *const c_char, name: &str) -> Result<Option<&'a str>, String> {
+    if text.is_null() {
+        return Ok(None);
+    }
+
+    // SAFETY: non-null, and NUL-terminated per the caller
+    let text = unsafe { CStr::from_ptr(text) };
+    text.to_str()
+        .map(Some)
+        .map_err(|_| format!("{} is not valid UTF-8", name))
+}
+
+fn new_handle(builder: CacheBuilder) -> *mut CacheHandle {
+    let builder = builder.stats(true);
+
+    match builder.build_removable() {
+        Ok(cache) => Box::into_raw(Box::new(CacheHandle {
+            cache,
+            stats: builder.stats_handle().expect("stats(true) was set"),
+        })),
+        Err(err) => fail(err),
+    }
+}
+
+/// Creates a cache of `capacity` keys evicting by `policy` ("lru" or "lfu"), its order kept by
+/// `structure` ("priority_queue", "vec_hashmap", "d_ary_heap_{2,4,8}", "pairing_heap" or, for
+/// "lfu", "freq_buckets"; NULL for "vec_hashmap").
+///
+/// Returns NULL if an argument is invalid, cache_last_error() saying which.
+///
+/// # Safety
+///
+/// `policy` and `structure` are NULL or NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_new(
+    policy: *const c_char,
+    structure: *const c_char,
+    capacity: usize,
+) -> *mut CacheHandle {
+    guard(
+        || {
+            // SAFETY: per this function's contract
+            let args = unsafe { (text_arg(policy, "policy"), text_arg(structure, "structure")) };
+
+            let (policy, structure) = match args {
+                (Ok(Some(policy)), Ok(structure)) => (policy, structure),
+                (Ok(None), _) => return fail("policy is NULL".to_string()),
+                (Err(err), _) | (_, Err(err)) => return fail(err),
+            };
+
+            let mut builder = CacheBuilder::new(policy, capacity);
+            if let Some(structure) = structure {
+                builder = builder.structure(structure);
+            }
+
+            new_handle(builder)
+        },
+        |panic| fail(format!("panicked: {}", panic)),
+    )
+}
+
+/// Creates a cache from a CacheConfig in JSON (starting with '{') or TOML, e.g.
+/// `policy = "lfu"`, `capacity = 1024`, `storage = "policy"`, `ttl_ms = 60000`.
+///
+/// Returns NULL if the config is invalid, cache_last_error() saying why.
+///
+/// # Safety
+///
+/// `config` is NULL or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_new_from_config(config: *const c_char) -> *mut CacheHandle {
+    guard(
+        || {
+            // SAFETY: per this function's contract
+            let config = match unsafe { text_arg(config, "config") } {
+                Ok(Some(config)) => config,
+                Ok(None) => return fail("config is NULL".to_string()),
+                Err(err) => return fail(err),
+            };
+
+            let config = match config.trim_start().starts_with('{') {
+                true => CacheConfig::from_json(config),
+                false => CacheConfig::from_toml(config),
+            };
+
+            match config {
+                Ok(config) => new_handle(CacheBuilder::from_config(config)),
+                Err(err) => fail(err),
+            }
+        },
+        |panic| fail(format!("panicked: {}", panic)),
+    )
+}
+
+/// Releases a cache; NULL is ignored.
+///
+/// # Safety
+///
+/// `cache` is NULL or a handle from cache_new*() not freed yet, and is not used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_free(cache: *mut CacheHandle) {
+    if !cache.is_null() {
+        // SAFETY: allocated by new_handle() through Box::into_raw(), and freed only once
+        drop(unsafe { Box::from_raw(cache) });
+    }
+}
+
+/// Inserts or updates `key`, evicting a key if the cache is full.
+///
+/// # Safety
+///
+/// `cache` is NULL (ignored) or a live handle, used by no other thread meanwhile.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_put(cache: *mut CacheHandle, key: i32, value: i32) {
+    // SAFETY: per this function's contract
+    if let Some(cache) = unsafe { cache.as_mut() } {
+        guard(|| cache.cache.put(key, value), |_| ());
+    }
+}
+
+/// Looks `key` up, counting as an access. Returns whether it was found, its value then
+/// written to `value` unless that is NULL.
+///
+/// # Safety
+///
+/// `cache` is NULL (a miss) or a live handle, used by no other thread meanwhile; `value` is
+/// NULL or points to a writable int32_t.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_get(cache: *mut CacheHandle, key: i32, value: *mut i32) -> bool {
+    // SAFETY: per this function's contract
+    let Some(cache) = (unsafe { cache.as_mut() }) else {
+        return false;
+    };
+
+    let found = guard(|| cache.cache.get(&key), |_| None);
+    // SAFETY: per this function's contract
+    unsafe { write_value(found, value) }
+}
+
+/// Drops `key` as if it had never been put. Returns whether it was there, its value then
+/// written to `value` unless that is NULL.
+///
+/// # Safety
+///
+/// As for cache_get().
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_remove(cache: *mut CacheHandle, key: i32, value: *mut i32) -> bool {
+    // SAFETY: per this function's contract
+    let Some(cache) = (unsafe { cache.as_mut() }) else {
+        return false;
+    };
+
+    let removed = guard(|| cache.cache.remove(&key), |_| None);
+    // SAFETY: per this function's contract
+    unsafe { write_value(removed, value) }
+}
+
+// SAFETY: `out` is NULL or points to a writable i32
+unsafe fn write_value(value: Option<i32>, out: *mut i32) -> bool {
+    match value {
+        Some(value) => {
+            if !out.is_null() {
+                // SAFETY: non-null, writable per the caller
+                unsafe { out.write(value) };
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// How many keys the cache holds, expired ones included until evicted.
+///
+/// # Safety
+///
+/// `cache` is NULL (0) or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_len(cache: *const CacheHandle) -> usize {
+    // SAFETY: per this function's contract
+    unsafe { cache.as_ref() }.map_or(0, |cache| cache.cache.len())
+}
+
+/// Whether the cache holds `key`, without counting as an access.
+///
+/// # Safety
+///
+/// `cache` is NULL (false) or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_contains(cache: *const CacheHandle, key: i32) -> bool {
+    // SAFETY: per this function's contract
+    unsafe { cache.as_ref() }.is_some_and(|cache| cache.cache.contains(&key))
+}
+
+/// The cache's statistics, all zero for NULL.
+///
+/// # Safety
+///
+/// `cache` is NULL or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_stats(cache: *const CacheHandle) -> CacheStatsSnapshot {
+    // SAFETY: per this function's contract
+    let Some(cache) = (unsafe { cache.as_ref() }) else {
+        return CacheStatsSnapshot::default();
+    };
+
+    let stats = &cache.stats;
+    CacheStatsSnapshot {
+        hits: stats.hits(),
+        misses: stats.misses(),
+        puts: stats.puts(),
+        evictions: stats.evictions(),
+        load_failures: stats.load_failures(),
+        size: stats.size(),
+        capacity: stats.capacity(),
+    }
+}
+
+/// Zeroes the counters of cache_stats(), not the size and capacity.
+///
+/// # Safety
+///
+/// `cache` is NULL (ignored) or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn cache_stats_reset(cache: *mut CacheHandle) {
+    // SAFETY: per this function's contract
+    if let Some(cache) = unsafe { cache.as_ref() } {
+        cache.stats.reset();
+    }
+}
+
+/// Why the last cache_new*() on this thread returned NULL, NULL if none did. Valid until the
+/// next failing cache_new*() on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn cache_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |err| err.as_ptr())
+    })
+}
@@ -0,0 +1,113 @@
+/* Built and run by tests/test_ffi.rs against libcache_ffi, exits non-zero on the first failure */
+
+#include "cache_ffi.h"
+
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+static int failures = 0;
+
+#define CHECK(cond)                                                      \
+    do {                                                                 \
+        if (!(cond)) {                                                   \
+            fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #cond);   \
+            failures++;                                                  \
+        }                                                                \
+    } while (0)
+
+static void test_lru(const char *structure) {
+    CacheHandle *cache = cache_new("lru", structure, 2);
+    int32_t value = 0;
+
+    CHECK(cache != NULL);
+
+    cache_put(cache, 1, 10);
+    cache_put(cache, 2, 20);
+    CHECK(cache_get(cache, 1, &value) && value == 10);
+
+    /* 2 is the least recently used */
+    cache_put(cache, 3, 30);
+    CHECK(!cache_get(cache, 2, &value));
+    CHECK(cache_contains(cache, 1) && cache_contains(cache, 3));
+    CHECK(cache_len(cache) == 2);
+
+    CHECK(cache_remove(cache, 1, &value) && value == 10);
+    CHECK(!cache_remove(cache, 1, NULL));
+    CHECK(cache_len(cache) == 1);
+
+    CacheStatsSnapshot stats = cache_stats(cache);
+    CHECK(stats.hits == 1 && stats.misses == 1);
+    CHECK(stats.puts == 3 && stats.evictions == 1);
+    CHECK(stats.size == 1 && stats.capacity == 2);
+
+    cache_stats_reset(cache);
+    stats = cache_stats(cache);
+    CHECK(stats.hits == 0 && stats.puts == 0 && stats.size == 1);
+
+    cache_free(cache);
+}
+
+static void test_lfu_from_config(const char *config) {
+    CacheHandle *cache = cache_new_from_config(config);
+    int32_t value = 0;
+
+    CHECK(cache != NULL);
+
+    cache_put(cache, 1, 10);
+    cache_put(cache, 2, 20);
+    CHECK(cache_get(cache, 1, NULL));
+
+    /* 2 is the least frequently used */
+    cache_put(cache, 3, 30);
+    CHECK(!cache_contains(cache, 2));
+    CHECK(cache_get(cache, 3, &value) && value == 30);
+
+    cache_free(cache);
+}
+
+static void test_errors(void) {
+    CHECK(cache_last_error() == NULL);
+
+    CHECK(cache_new("lru", NULL, 0) == NULL);
+    CHECK(strstr(cache_last_error(), "capacity") != NULL);
+
+    /* rejected rather than reserved, or a panic unwinding into C */
+    CHECK(cache_new("lru", NULL, SIZE_MAX) == NULL);
+    CHECK(strstr(cache_last_error(), "capacity") != NULL);
+
+    CHECK(cache_new("lru", "freq_buckets", 2) == NULL);
+    CHECK(strstr(cache_last_error(), "freq_buckets") != NULL);
+
+    CHECK(cache_new(NULL, NULL, 2) == NULL);
+    CHECK(cache_new_from_config("policy = \"lru\"") == NULL);
+    CHECK(cache_new_from_config("{\"policy\": \"lru\", \"capacity\": 2, \"size\": 3}") == NULL);
+
+    /* NULL handles are misses and no-ops */
+    cache_put(NULL, 1, 1);
+    CHECK(!cache_get(NULL, 1, NULL));
+    CHECK(cache_len(NULL) == 0);
+    CHECK(cache_stats(NULL).capacity == 0);
+    cache_free(NULL);
+}
+
+int main(void) {
+    const char *structures[] = {NULL, "priority_queue", "d_ary_heap_4", "pairing_heap"};
+
+    for (size_t i = 0; i < sizeof(structures) / sizeof(structures[0]); i++) {
+        test_lru(structures[i]);
+    }
+
+    test_lfu_from_config("policy = \"lfu\"\nstructure = \"freq_buckets\"\ncapacity = 2\n");
+    test_lfu_from_config("{\"policy\": \"lfu\", \"storage\": \"policy\", \"capacity\": 2}");
+
+    test_errors();
+
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,69 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/cache_ffi.h"));
+
+fn crate_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+// the cdylib is built next to this test's executable, in target/<profile>/deps
+fn library_dir() -> PathBuf {
+    env::current_exe()
+        .unwrap()
+        .parent()
+        .expect("the test runs from a directory")
+        .to_path_buf()
+}
+
+#[test]
+fn test_header_up_to_date() {
+    let checked_in = fs::read_to_string(crate_dir().join("include/cache_ffi.h")).unwrap();
+
+    assert!(
+        checked_in == HEADER,
+        "include/cache_ffi.h is stale, copy {}/cache_ffi.h over it",
+        env!("OUT_DIR")
+    );
+}
+
+// compiles tests/c/test_cache_ffi.c with $CC (default cc) against libcache_ffi and runs it
+#[test]
+#[cfg_attr(
+    cache_ffi_sanitized,
+    ignore = "the C program isn't built with the sanitizer"
+)]
+fn test_c_program() {
+    let library_dir = library_dir();
+    let binary = Path::new(env!("CARGO_TARGET_TMPDIR")).join("test_cache_ffi");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let compiled = Command::new(&compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-o"])
+        .arg(&binary)
+        .arg(crate_dir().join("tests/c/test_cache_ffi.c"))
+        .arg("-I")
+        .arg(crate_dir().join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg(format!("-Wl,-rpath,{}", library_dir.display()))
+        .arg("-lcache_ffi")
+        .output()
+        .unwrap_or_else(|err| panic!("can't run {}: {}", compiler, err));
+    assert!(
+        compiled.status.success(),
+        "{} failed:\n{}",
+        compiler,
+        String::from_utf8_lossy(&compiled.stderr)
+    );
+
+    let run = Command::new(&binary).output().unwrap();
+    assert!(
+        run.status.success(),
+        "test_cache_ffi failed:\n{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "ok\n");
+}
@@ -0,0 +1,49 @@
+mod modules;
+use anyhow::{Context, anyhow};
+use cache_util::{TraceFormat, Workload, load_trace};
+use clap::Parser;
+use modules::args::Args;
+use modules::output::render;
+use modules::policies::{POLICIES, select_policies};
+use modules::simulate::simulate;
+use std::path::Path;
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    if args.list_policies {
+        for policy in POLICIES.iter() {
+            println!("{}", policy.name);
+        }
+        return Ok(());
+    }
+
+    let policies = select_policies(&args.policy)?;
+
+    let operations = match &args.trace {
+        Some(path) => {
+            let format = match &args.format {
+                Some(format) => format.parse::<TraceFormat>().map_err(|err| anyhow!(err))?,
+                None => TraceFormat::from_path(Path::new(path)),
+            };
+
+            load_trace(path, format).with_context(|| format!("Failed to load trace '{}'", path))?
+        }
+        None => args
+            .workload
+            .parse::<Workload>()
+            .map_err(|err| anyhow!(err))?
+            .operations(args.seed, args.operations),
+    };
+
+    let mut results = Vec::with_capacity(policies.len() * args.capacities.len());
+    for &capacity in &args.capacities {
+        for &policy in &policies {
+            results.push(simulate(policy, capacity, &operations, args.fill_on_miss));
+        }
+    }
+
+    print!("{}", render(&results, args.output)?);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use clap::{Parser, ValueEnum};
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "cache_sim")]
+pub struct Args {
+    /// Policies to simulate: a name from --list-policies, a family (`lru`, `lfu`) or `all`.
+    #[arg(long, value_delimiter = ',', default_value = "all")]
+    pub policy: Vec<String>,
+
+    /// Cache capacities to simulate, e.g. `100,1000,10000`, at most 16777216.
+    #[arg(long, value_delimiter = ',', default_value = "1000", value_parser = parse_capacity)]
+    pub capacities: Vec<usize>,
+
+    /// A synthetic workload, e.g. `zipfian:0.99@0.1` (see docs/cache_workloads.md).
+    #[arg(long, conflicts_with = "trace", default_value = "uniform")]
+    pub workload: String,
+
+    /// Number of operations drawn from --workload.
+    #[arg(long, default_value_t = 100_000)]
+    pub operations: usize,
+
+    /// Seed for --workload.
+    #[arg(long, default_value_t = cache_util::SEED)]
+    pub seed: u64,
+
+    /// A trace file to replay instead of --workload.
+    #[arg(long, value_name = "TRACE_PATH")]
+    pub trace: Option<String>,
+
+    /// Trace format (`text`, `arc`, `lirs`, `binary`), guessed from the extension by default.
+    #[arg(long, requires = "trace")]
+    pub format: Option<String>,
+
+    /// Put missed keys into the cache (value = key), e.g. for read-only block traces.
+    #[arg(long)]
+    pub fill_on_miss: bool,
+
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Print the available policies and exit.
+    #[arg(long)]
+    pub list_policies: bool,
+}
+
+// The largest --capacities: the implementations reserve their whole capacity up front, a few
+// hundred MB at this size, and tens of GB near i32::MAX
+pub const MAX_CAPACITY: usize = 1 << 24;
+
+fn parse_capacity(capacity: &str) -> Result<usize, String> {
+    let capacity = capacity.parse::<usize>().map_err(|err| err.to_string())?;
+
+    match capacity <= MAX_CAPACITY {
+        true => Ok(capacity),
+        false => Err(format!(
+            "{} is above the maximum of {}",
+            capacity, MAX_CAPACITY
+        )),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod args;
+pub mod output;
+pub mod policies;
+pub mod simulate;
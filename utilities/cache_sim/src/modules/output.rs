@@ -0,0 +1,79 @@
+use super::args::OutputFormat;
+use super::simulate::SimulationResult;
+use std::fmt::Write;
+
+const HEADERS: [&str; 8] = [
+    "policy",
+    "capacity",
+    "gets",
+    "hit_ratio",
+    "insertions",
+    "evictions",
+    "elapsed_secs",
+    "ops_per_sec",
+];
+
+fn row(result: &SimulationResult) -> [String; 8] {
+    [
+        result.policy.to_string(),
+        result.capacity.to_string(),
+        result.gets.to_string(),
+        format!("{:.4}", result.hit_ratio),
+        result.insertions.to_string(),
+        result.evictions.to_string(),
+        format!("{:.4}", result.elapsed_secs),
+        format!("{:.0}", result.ops_per_sec),
+    ]
+}
+
+fn render_table(results: &[SimulationResult]) -> String {
+    let rows = results.iter().map(row).collect::<Vec<_>>();
+
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut out = String::new();
+    let mut write_line = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                0 => format!("{:<width$}", cell),
+                _ => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    };
+
+    write_line(&HEADERS.map(String::from));
+    for row in &rows {
+        write_line(row);
+    }
+
+    out
+}
+
+fn render_csv(results: &[SimulationResult]) -> String {
+    let mut out = HEADERS.join(",") + "\n";
+
+    for result in results {
+        out += &(row(result).join(",") + "\n");
+    }
+
+    out
+}
+
+pub fn render(results: &[SimulationResult], format: OutputFormat) -> anyhow::Result<String> {
+    match format {
+        OutputFormat::Table => Ok(render_table(results)),
+        OutputFormat::Csv => Ok(render_csv(results)),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(results)? + "\n"),
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::anyhow;
+use cache_util::CacheImplementation;
+use once_cell::sync::Lazy;
+
+pub struct Policy {
+    pub name: String, // "lru/two_hashmaps", "lfu/vec_hashmap_eviction", ...
+    pub implementation: &'static CacheImplementation,
+}
+
+// every registered q146 (lru) and q460 (lfu) implementation
+pub static POLICIES: Lazy<Vec<Policy>> = Lazy::new(|| {
+    let family = |family: &'static str| {
+        move |implementation: &'static CacheImplementation| Policy {
+            name: format!("{}/{}", family, implementation.name),
+            implementation,
+        }
+    };
+
+    q146_lru_cache::implementations()
+        .map(family("lru"))
+        .chain(q460_lfu_cache::implementations().map(family("lfu")))
+        .collect()
+});
+
+// "all", a family ("lru", "lfu") or a full name, in the order given without duplicates
+pub fn select_policies(names: &[String]) -> anyhow::Result<Vec<&'static Policy>> {
+    let mut selected: Vec<&'static Policy> = Vec::new();
+
+    for name in names {
+        let matches = POLICIES
+            .iter()
+            .filter(|policy| {
+                name == "all"
+                    || policy.name == *name
+                    || policy.name.split_once('/').map(|(family, _)| family) == Some(name.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        if matches.is_empty() {
+            return Err(anyhow!(
+                "Unknown policy '{}', see --list-policies for the available ones",
+                name
+            ));
+        }
+
+        for policy in matches {
+            if !selected.iter().any(|p| p.name == policy.name) {
+                selected.push(policy);
+            }
+        }
+    }
+
+    Ok(selected)
+}
@@ -0,0 +1,118 @@
+use super::policies::Policy;
+use cache_util::{CacheOperation, LeetCodeCache};
+use serde::Serialize;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationResult {
+    pub policy: &'static str,
+    pub capacity: usize,
+    pub gets: usize,
+    pub hits: usize,
+    pub misses: usize,
+    pub hit_ratio: f64,
+    pub insertions: usize,
+    pub evictions: usize,
+    pub elapsed_secs: f64,
+    pub ops_per_sec: f64,
+}
+
+#[derive(Default)]
+struct Counters {
+    gets: usize,
+    hits: usize,
+    insertions: usize,
+    evictions: usize,
+}
+
+// put() that also counts whether the key was new and whether it pushed another key out
+fn counted_put(cache: &mut dyn LeetCodeCache, counters: &mut Counters, key: i32, value: i32) {
+    let (was_present, len_before) = (cache.contains(key), cache.len());
+
+    cache.put(key, value);
+
+    if !was_present && cache.contains(key) {
+        counters.insertions += 1;
+
+        if cache.len() == len_before {
+            counters.evictions += 1;
+        }
+    }
+}
+
+fn count(
+    cache: &mut dyn LeetCodeCache,
+    operations: &[CacheOperation],
+    fill_on_miss: bool,
+) -> Counters {
+    let mut counters = Counters::default();
+
+    for operation in operations {
+        match *operation {
+            CacheOperation::Put { key, value } => counted_put(cache, &mut counters, key, value),
+            CacheOperation::Get { key } => {
+                counters.gets += 1;
+
+                if cache.get(key) != -1 {
+                    counters.hits += 1;
+                } else if fill_on_miss {
+                    counted_put(cache, &mut counters, key, key);
+                }
+            }
+        }
+    }
+
+    counters
+}
+
+// same operations without the bookkeeping, so the timing only covers the cache itself
+fn time(cache: &mut dyn LeetCodeCache, operations: &[CacheOperation], fill_on_miss: bool) -> f64 {
+    let start = Instant::now();
+
+    for operation in operations {
+        match *operation {
+            CacheOperation::Put { key, value } => cache.put(key, value),
+            CacheOperation::Get { key } => {
+                if cache.get(key) == -1 && fill_on_miss {
+                    cache.put(key, key);
+                }
+            }
+        }
+    }
+
+    start.elapsed().as_secs_f64()
+}
+
+pub fn simulate(
+    policy: &'static Policy,
+    capacity: usize,
+    operations: &[CacheOperation],
+    fill_on_miss: bool,
+) -> SimulationResult {
+    let new = || {
+        let capacity =
+            i32::try_from(capacity).expect("--capacities are checked to be at most MAX_CAPACITY");
+        (policy.implementation.new)(capacity)
+    };
+
+    let counters = count(&mut *new(), operations, fill_on_miss);
+    let elapsed_secs = time(&mut *new(), operations, fill_on_miss);
+
+    let hit_ratio = match counters.gets {
+        0 => 0.0,
+        gets => counters.hits as f64 / gets as f64,
+    };
+
+    SimulationResult {
+        policy: &policy.name,
+        capacity,
+        gets: counters.gets,
+        hits: counters.hits,
+        misses: counters.gets - counters.hits,
+        hit_ratio,
+        insertions: counters.insertions,
+        evictions: counters.evictions,
+        elapsed_secs,
+        ops_per_sec: operations.len() as f64 / elapsed_secs.max(f64::MIN_POSITIVE),
+    }
+}
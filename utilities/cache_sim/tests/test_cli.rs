@@ -0,0 +1,109 @@
+use rstest::rstest;
+use serde_json::Value;
+use std::process::{Command, Output};
+
+use cache_util::{Workload, replay};
+
+fn cache_sim(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cache_sim"))
+        .args(args)
+        .output()
+        .expect("failed to run cache_sim")
+}
+
+#[rstest]
+#[case(&["--capacities", "2147483648"], "2147483648 is above the maximum")]
+#[case(&["--policy", "lru/arena", "--capacities", "2000000000", "--operations", "10"], "2000000000 is above the maximum of 16777216")]
+#[case(&["--capacities", "16777217"], "16777217 is above the maximum")]
+#[case(&["--capacities", "10,-1"], "invalid digit")]
+#[case(&["--output", "xml"], "invalid value 'xml'")]
+#[case(&["--format", "arc"], "--trace <TRACE_PATH>")]
+#[case(&["--trace", "x.arc", "--workload", "scan"], "cannot be used with")]
+fn test_cli_rejects(#[case] args: &[&str], #[case] mentions: &str) {
+    let output = cache_sim(args);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(2), "{}", stderr); // clap's usage error
+    assert!(stderr.contains(mentions), "{}", stderr);
+}
+
+#[test]
+fn test_list_policies() {
+    let output = cache_sim(&["--list-policies"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.status.success());
+    assert!(stdout.lines().any(|line| line == "lru/two_hashmaps"));
+    assert!(stdout.lines().any(|line| line == "lfu/freq_buckets"));
+}
+
+// the hits of every simulated policy are those of replay() over the same operations
+#[test]
+fn test_simulate_matches_replay() {
+    let (workload, seed, len) = ("zipfian:0.99@0.2", 7, 5_000);
+    let output = cache_sim(&[
+        "--policy",
+        "lru/two_hashmaps,lfu/vec_hashmap",
+        "--capacities",
+        "10,100",
+        "--workload",
+        workload,
+        "--seed",
+        &seed.to_string(),
+        "--operations",
+        &len.to_string(),
+        "--fill-on-miss",
+        "--output",
+        "json",
+    ]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let results: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results.len(), 4);
+
+    let operations = workload.parse::<Workload>().unwrap().operations(seed, len);
+    let implementations = q146_lru_cache::implementations()
+        .map(|implementation| ("lru", implementation))
+        .chain(q460_lfu_cache::implementations().map(|implementation| ("lfu", implementation)))
+        .collect::<Vec<_>>();
+
+    for result in &results {
+        let policy = result["policy"].as_str().unwrap();
+        let capacity = result["capacity"].as_u64().unwrap();
+
+        let &(_, implementation) = implementations
+            .iter()
+            .find(|(family, implementation)| {
+                format!("{}/{}", family, implementation.name) == policy
+            })
+            .unwrap();
+        let report = replay(
+            &mut *(implementation.new)(capacity as i32),
+            &operations,
+            true,
+        );
+
+        assert_eq!(
+            result["gets"].as_u64(),
+            Some(report.gets as u64),
+            "{}",
+            policy
+        );
+        assert_eq!(
+            result["hits"].as_u64(),
+            Some(report.hits as u64),
+            "{}",
+            policy
+        );
+        assert_eq!(
+            result["misses"].as_u64(),
+            Some(report.misses() as u64),
+            "{}",
+            policy
+        );
+    }
+}
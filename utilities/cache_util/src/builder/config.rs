@@ -0,0 +1,132 @@
+use serde::Deserialize;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyKind {
+    Lru,
+    Lfu,
+}
+
+impl FromStr for PolicyKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "lru" => Ok(PolicyKind::Lru),
+            "lfu" => Ok(PolicyKind::Lfu),
+            _ => Err(format!("unknown policy \"{}\"", name)),
+        }
+    }
+}
+
+// What keeps the eviction order, named after the implementation modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureKind {
+    PriorityQueue,
+    DAryHeap(usize), // arity 2, 4 or 8; "vec_hashmap" is the binary one
+    PairingHeap,
+    FreqBuckets, // LFU only
+}
+
+impl FromStr for StructureKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "priority_queue" => Ok(StructureKind::PriorityQueue),
+            "vec_hashmap" | "d_ary_heap_2" => Ok(StructureKind::DAryHeap(2)),
+            "d_ary_heap_4" => Ok(StructureKind::DAryHeap(4)),
+            "d_ary_heap_8" => Ok(StructureKind::DAryHeap(8)),
+            "pairing_heap" => Ok(StructureKind::PairingHeap),
+            "freq_buckets" => Ok(StructureKind::FreqBuckets),
+            _ => Err(format!("unknown structure \"{}\"", name)),
+        }
+    }
+}
+
+// Where the values live
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    HashMap, // a HashMapStorage next to the policy, i.e. GenericCache
+    Policy,  // in the policy's own nodes, i.e. EvictionCache
+}
+
+impl FromStr for StorageKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "hashmap" => Ok(StorageKind::HashMap),
+            "policy" => Ok(StorageKind::Policy),
+            _ => Err(format!("unknown storage \"{}\"", name)),
+        }
+    }
+}
+
+// CacheBuilder options as written in a config file, names checked by CacheBuilder::build():
+//
+//     policy = "lfu"
+//     structure = "pairing_heap" # default "vec_hashmap"
+//     storage = "policy"         # default "hashmap"
+//     capacity = 1024
+//     ttl_ms = 60000             # default none
+//     stats = true               # default false
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    pub policy: String,
+    #[serde(default = "default_structure")]
+    pub structure: String,
+    #[serde(default = "default_storage")]
+    pub storage: String,
+    pub capacity: usize,
+    #[serde(default)]
+    pub ttl_ms: Option<u64>,
+    #[serde(default)]
+    pub stats: bool,
+}
+
+fn default_structure() -> String {
+    "vec_hashmap".to_string()
+}
+
+fn default_storage() -> String {
+    "hashmap".to_string()
+}
+
+impl CacheConfig {
+    pub fn new(policy: &str, capacity: usize) -> Self {
+        Self {
+            policy: policy.to_string(),
+            structure: default_structure(),
+            storage: default_storage(),
+            capacity,
+            ttl_ms: None,
+            stats: false,
+        }
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|err| format!("invalid cache config: {}", err))
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|err| format!("invalid cache config: {}", err))
+    }
+
+    // TOML for a .toml file, JSON otherwise
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&text),
+            _ => Self::from_json(&text),
+        }
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Cache, RemovableCache, SizedCache};
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// Entries readable for `ttl` after their last put.
+//
+// Expiry is lazy: an expired get() is a miss that never reaches `inner`, but the entry keeps
+// its slot there until the policy evicts it or a put() overwrites it. Not touching it on
+// those misses means it ages like any entry nobody reads.
+pub(crate) struct ExpiringCache<C: SizedCache> {
+    inner: C,
+    ttl: Duration,
+    capacity: usize,
+    deadlines: HashMap<i32, Instant>, // also of keys `inner` has evicted since
+    prune_at: usize,                  // deadlines.len() at which the expired ones get dropped
+}
+
+impl<C: SizedCache> ExpiringCache<C> {
+    pub(crate) fn new(inner: C, ttl: Duration, capacity: usize) -> Self {
+        Self {
+            inner,
+            ttl,
+            capacity,
+            deadlines: HashMap::new(),
+            prune_at: 2 * capacity,
+        }
+    }
+}
+
+impl<C: SizedCache> Cache for ExpiringCache<C> {
+    fn put(&mut self, key: i32, value: i32) {
+        let now = Instant::now();
+
+        self.inner.put(key, value);
+        self.deadlines.insert(key, now + self.ttl);
+
+        // the deadlines of evicted keys pile up, drop the expired ones now and then
+        if self.deadlines.len() > self.prune_at {
+            self.deadlines.retain(|_, deadline| *deadline > now);
+            self.prune_at = (2 * self.capacity).max(2 * self.deadlines.len());
+        }
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        match self.deadlines.get(key) {
+            Some(deadline) if *deadline > Instant::now() => self.inner.get(key),
+            _ => None,
+        }
+    }
+}
+
+// expired entries included, they hold a slot all the same
+impl<C: SizedCache> SizedCache for ExpiringCache<C> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.inner.contains(key)
+    }
+}
+
+// gone either way, but an expired entry has no value to return, as in get()
+impl<C: RemovableCache> RemovableCache for ExpiringCache<C> {
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        let deadline = self.deadlines.remove(key);
+        let value = self.inner.remove(key);
+
+        value.filter(|_| deadline.is_some_and(|deadline| deadline > Instant::now()))
+    }
+}
@@ -0,0 +1,230 @@
+mod config;
+pub use config::*;
+
+mod expiring;
+use expiring::ExpiringCache;
+
+use crate::{Cache, CacheStats, InstrumentedCache, RemovableCache};
+use crate::{EvictionAsStoragePolicy, EvictionPolicy};
+use crate::{EvictionCache, GenericCache, HashMapStorage, HeapNodeTrait};
+use crate::{EvictionPolicyFreqBuckets, EvictionPolicyPH, EvictionPolicyPQ, EvictionPolicyVHM};
+use crate::{KeyAwareHeapNode, LFUHeapNode, LRUHeapNode, ValueAwareHeapNode};
+
+use std::time::Duration;
+
+// The largest capacity build() accepts, that of the LeetCode-style constructors taking an i32
+pub const MAX_CAPACITY: usize = i32::MAX as usize;
+
+// How many keys a built cache's map makes room for up front, growing past it on demand
+const RESERVED_CAPACITY: usize = 1 << 16;
+
+// Picks a cache by name instead of by type, either in code:
+//
+//     let mut cache = CacheBuilder::new("lfu", 1024)
+//         .structure("pairing_heap")
+//         .ttl(Duration::from_secs(60))
+//         .build()?;
+//
+// or from a CacheConfig, e.g. CacheBuilder::from_config(CacheConfig::load("cache.toml")?).
+// Every option is checked by build(), which names the first bad one.
+#[derive(Debug, Clone)]
+pub struct CacheBuilder {
+    config: CacheConfig,
+    stats: Option<CacheStats>,
+}
+
+impl CacheBuilder {
+    pub fn new(policy: &str, capacity: usize) -> Self {
+        Self::from_config(CacheConfig::new(policy, capacity))
+    }
+
+    pub fn from_config(config: CacheConfig) -> Self {
+        let stats = config.stats.then(CacheStats::default);
+        Self { config, stats }
+    }
+
+    pub fn policy(mut self, policy: &str) -> Self {
+        self.config.policy = policy.to_string();
+        self
+    }
+
+    pub fn structure(mut self, structure: &str) -> Self {
+        self.config.structure = structure.to_string();
+        self
+    }
+
+    pub fn storage(mut self, storage: &str) -> Self {
+        self.config.storage = storage.to_string();
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.config.capacity = capacity;
+        self
+    }
+
+    // Duration::ZERO is rejected by build() rather than meaning "no TTL"
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.config.ttl_ms = Some(ttl.as_millis().try_into().unwrap_or(u64::MAX));
+        self
+    }
+
+    pub fn stats(mut self, enabled: bool) -> Self {
+        self.config.stats = enabled;
+        self.stats = enabled.then(|| self.stats.unwrap_or_default());
+        self
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    // The counters of every cache this builder builds, None without stats(true)
+    pub fn stats_handle(&self) -> Option<CacheStats> {
+        self.stats.clone()
+    }
+
+    pub fn build(&self) -> Result<Box<dyn Cache>, String> {
+        self.build_removable()
+            .map(|cache| Box::new(cache) as Box<dyn Cache>)
+    }
+
+    // build(), keeping len(), contains() and remove() within reach
+    pub fn build_removable(&self) -> Result<Box<dyn RemovableCache>, String> {
+        let policy = self.config.policy.parse::<PolicyKind>()?;
+        let structure = self.config.structure.parse::<StructureKind>()?;
+        let storage = self.config.storage.parse::<StorageKind>()?;
+        let capacity = self.config.capacity;
+
+        if capacity == 0 {
+            return Err("capacity must be at least 1".to_string());
+        }
+        if capacity > MAX_CAPACITY {
+            return Err(format!(
+                "capacity {} is above the maximum of {}",
+                capacity, MAX_CAPACITY
+            ));
+        }
+        if self.config.ttl_ms == Some(0) {
+            return Err("ttl must be at least 1 ms".to_string());
+        }
+        if policy == PolicyKind::Lru && structure == StructureKind::FreqBuckets {
+            return Err("structure \"freq_buckets\" only supports policy \"lfu\"".to_string());
+        }
+
+        let wrappers = Wrappers {
+            capacity,
+            ttl: self.config.ttl_ms.map(Duration::from_millis),
+            stats: self.stats.clone(),
+        };
+
+        Ok(match (policy, structure, storage) {
+            (PolicyKind::Lfu, StructureKind::FreqBuckets, StorageKind::HashMap) => {
+                generic_cache(EvictionPolicyFreqBuckets::<()>::new(), &wrappers)
+            }
+            (PolicyKind::Lfu, StructureKind::FreqBuckets, StorageKind::Policy) => {
+                eviction_cache(EvictionPolicyFreqBuckets::<i32>::new(), &wrappers)
+            }
+            (PolicyKind::Lru, _, _) => heap_cache::<LRUHeapNode>(structure, storage, &wrappers),
+            (PolicyKind::Lfu, _, _) => {
+                heap_cache::<LFUHeapNode<LRUHeapNode>>(structure, storage, &wrappers)
+            }
+        })
+    }
+}
+
+// The TTL and stats options, around whichever cache build() picked
+struct Wrappers {
+    capacity: usize,
+    ttl: Option<Duration>,
+    stats: Option<CacheStats>,
+}
+
+impl Wrappers {
+    fn wrap<C: RemovableCache + 'static>(&self, cache: C) -> Box<dyn RemovableCache> {
+        let capacity = self.capacity;
+
+        // the stats outermost, so that expired reads count as misses
+        match (self.ttl, &self.stats) {
+            (None, None) => Box::new(cache),
+            (Some(ttl), None) => Box::new(ExpiringCache::new(cache, ttl, capacity)),
+            (None, Some(stats)) => Box::new(InstrumentedCache::new(
+                Box::new(cache),
+                capacity,
+                stats.clone(),
+            )),
+            (Some(ttl), Some(stats)) => Box::new(InstrumentedCache::new(
+                Box::new(ExpiringCache::new(cache, ttl, capacity)),
+                capacity,
+                stats.clone(),
+            )),
+        }
+    }
+}
+
+fn generic_cache<P: EvictionPolicy + 'static>(
+    policy: P,
+    wrappers: &Wrappers,
+) -> Box<dyn RemovableCache> {
+    let capacity = wrappers.capacity;
+    wrappers.wrap(GenericCache::new(
+        policy,
+        HashMapStorage::new(capacity.min(RESERVED_CAPACITY)),
+        capacity,
+    ))
+}
+
+fn eviction_cache<P: EvictionAsStoragePolicy + 'static>(
+    policy: P,
+    wrappers: &Wrappers,
+) -> Box<dyn RemovableCache> {
+    wrappers.wrap(EvictionCache::new(policy, wrappers.capacity))
+}
+
+// The heap-based caches, ordered by `H`, wrapped the same way the implementations wrap it
+fn heap_cache<H>(
+    structure: StructureKind,
+    storage: StorageKind,
+    wrappers: &Wrappers,
+) -> Box<dyn RemovableCache>
+where
+    H: HeapNodeTrait<Key = (), Value = ()> + 'static,
+{
+    type Keyed<H> = KeyAwareHeapNode<H>;
+    type Valued<H> = ValueAwareHeapNode<H>;
+
+    match (structure, storage) {
+        (StructureKind::PriorityQueue, StorageKind::HashMap) => {
+            generic_cache(EvictionPolicyPQ::<H>::new(), wrappers)
+        }
+        (StructureKind::PriorityQueue, StorageKind::Policy) => {
+            eviction_cache(EvictionPolicyPQ::<Valued<H>>::new(), wrappers)
+        }
+        (StructureKind::DAryHeap(4), StorageKind::HashMap) => {
+            generic_cache(EvictionPolicyVHM::<H, 4>::new(), wrappers)
+        }
+        (StructureKind::DAryHeap(4), StorageKind::Policy) => {
+            eviction_cache(EvictionPolicyVHM::<Valued<H>, 4>::new(), wrappers)
+        }
+        (StructureKind::DAryHeap(8), StorageKind::HashMap) => {
+            generic_cache(EvictionPolicyVHM::<H, 8>::new(), wrappers)
+        }
+        (StructureKind::DAryHeap(8), StorageKind::Policy) => {
+            eviction_cache(EvictionPolicyVHM::<Valued<H>, 8>::new(), wrappers)
+        }
+        // 2, the only arity left
+        (StructureKind::DAryHeap(_), StorageKind::HashMap) => {
+            generic_cache(EvictionPolicyVHM::<H, 2>::new(), wrappers)
+        }
+        (StructureKind::DAryHeap(_), StorageKind::Policy) => {
+            eviction_cache(EvictionPolicyVHM::<Valued<H>, 2>::new(), wrappers)
+        }
+        (StructureKind::PairingHeap, StorageKind::HashMap) => {
+            generic_cache(EvictionPolicyPH::<Keyed<H>>::new(), wrappers)
+        }
+        (StructureKind::PairingHeap, StorageKind::Policy) => {
+            eviction_cache(EvictionPolicyPH::<Valued<Keyed<H>>>::new(), wrappers)
+        }
+        (StructureKind::FreqBuckets, _) => unreachable!("not heap-based"),
+    }
+}
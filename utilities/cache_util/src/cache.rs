@@ -1,4 +1,59 @@
 pub trait Cache {
     fn put(&mut self, key: i32, value: i32);
     fn get(&mut self, key: &i32) -> Option<i32>;
+
+    // get() for each key in order; overridden where a batch is cheaper than one get at a time
+    fn get_many(&mut self, keys: &[i32]) -> Vec<Option<i32>> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+
+    // put() for each item in order; overridden where a batch is cheaper than one put at a time
+    fn put_many<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = (i32, i32)>,
+        Self: Sized,
+    {
+        for (key, value) in items {
+            self.put(key, value);
+        }
+    }
+
+    // Bulk loading, the first item ending up the least recently used
+    fn extend<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = (i32, i32)>,
+        Self: Sized,
+    {
+        self.put_many(items);
+    }
+}
+
+// A Cache that can tell how many keys it holds, which is how InstrumentedCache sees evictions
+pub trait SizedCache: Cache {
+    fn len(&self) -> usize;
+    fn contains(&self, key: &i32) -> bool; // without counting as an access
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// A SizedCache that can also drop a key, as if it had never been put
+pub trait RemovableCache: SizedCache {
+    fn remove(&mut self, key: &i32) -> Option<i32>;
+}
+
+// so that CacheBuilder::build() can hand out what build_removable() does
+impl Cache for Box<dyn RemovableCache> {
+    fn put(&mut self, key: i32, value: i32) {
+        (**self).put(key, value);
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        (**self).get(key)
+    }
+
+    fn get_many(&mut self, keys: &[i32]) -> Vec<Option<i32>> {
+        (**self).get_many(keys)
+    }
 }
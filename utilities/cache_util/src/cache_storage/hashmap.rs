@@ -18,8 +18,12 @@ impl CacheStorage for HashMapStorage {
         self.0.get(key).copied()
     }
 
-    fn remove(&mut self, key: &i32) {
-        self.0.remove(key);
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        self.0.remove(key)
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.0.contains_key(key)
     }
 
     fn len(&self) -> usize {
@@ -1,7 +1,8 @@
 pub trait CacheStorage {
     fn put(&mut self, key: i32, value: i32);
     fn get(&mut self, key: &i32) -> Option<i32>;
-    fn remove(&mut self, key: &i32);
+    fn remove(&mut self, key: &i32) -> Option<i32>;
+    fn contains(&self, key: &i32) -> bool; // without counting as an access
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool;
 }
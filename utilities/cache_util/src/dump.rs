@@ -0,0 +1,235 @@
+use crate::{CacheImplementation, CacheOperation, HeapNodeTrait, LeetCodeCache};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// One node of the structure, `id` being its index where it has one (heap index, arena slot)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DumpNode {
+    pub id: usize,
+    pub key: i32,
+    pub label: String,           // what else the node holds, one line each
+    pub cluster: Option<String>, // e.g. the "freq 2" list it is linked in
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DumpEdge {
+    pub from: usize,
+    pub to: usize,
+    pub kind: &'static str, // "child", "sibling", "next"
+}
+
+// A snapshot of a cache's internals, rendered as Graphviz DOT or JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StructureDump {
+    pub kind: &'static str,
+    pub nodes: Vec<DumpNode>,
+    pub edges: Vec<DumpEdge>,
+    pub index: BTreeMap<i32, usize>, // the key -> node map, as the cache's own map sees it
+}
+
+impl StructureDump {
+    pub fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    pub fn push_node(&mut self, id: usize, key: i32, label: String, cluster: Option<String>) {
+        self.nodes.push(DumpNode {
+            id,
+            key,
+            label,
+            cluster,
+        });
+    }
+
+    pub fn push_edge(&mut self, from: usize, to: usize, kind: &'static str) {
+        self.edges.push(DumpEdge { from, to, kind });
+    }
+
+    // (id, key, label) from head to tail, linked by "next" edges
+    pub fn push_list(
+        &mut self,
+        cluster: Option<String>,
+        nodes: impl IntoIterator<Item = (usize, i32, String)>,
+    ) {
+        let mut prev = None;
+
+        for (id, key, label) in nodes {
+            self.push_node(id, key, label, cluster.clone());
+            if let Some(prev) = prev {
+                self.push_edge(prev, id, "next");
+            }
+            prev = Some(id);
+        }
+    }
+
+    // `dot -Tsvg` ready: nodes grouped by cluster, the index as a record whose fields
+    // point at their nodes
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+
+        writeln!(dot, "digraph \"{}\" {{", escape(self.kind)).unwrap();
+        writeln!(dot, "    node [shape=box, fontname=monospace];").unwrap();
+
+        let mut clusters = Vec::<(Option<&str>, Vec<&DumpNode>)>::new();
+        for node in &self.nodes {
+            let cluster = node.cluster.as_deref();
+            match clusters.iter_mut().find(|(name, _)| *name == cluster) {
+                Some((_, nodes)) => nodes.push(node),
+                None => clusters.push((cluster, vec![node])),
+            }
+        }
+
+        for (number, (cluster, nodes)) in clusters.iter().enumerate() {
+            let indent = match cluster {
+                Some(name) => {
+                    writeln!(dot, "    subgraph cluster_{} {{", number).unwrap();
+                    writeln!(dot, "        label=\"{}\";", escape(name)).unwrap();
+                    "        "
+                }
+                None => "    ",
+            };
+
+            for node in nodes {
+                let mut label = format!("#{}\nkey {}", node.id, node.key);
+                if !node.label.is_empty() {
+                    label = format!("{}\n{}", label, node.label);
+                }
+                writeln!(
+                    dot,
+                    "{}n{} [label=\"{}\"];",
+                    indent,
+                    node.id,
+                    escape(&label)
+                )
+                .unwrap();
+            }
+
+            if cluster.is_some() {
+                writeln!(dot, "    }}").unwrap();
+            }
+        }
+
+        for edge in &self.edges {
+            writeln!(
+                dot,
+                "    n{} -> n{} [label=\"{}\"];",
+                edge.from,
+                edge.to,
+                escape(edge.kind)
+            )
+            .unwrap();
+        }
+
+        if !self.index.is_empty() {
+            let fields = self
+                .index
+                .keys()
+                .enumerate()
+                .map(|(port, key)| format!("<p{}> {}", port, key))
+                .collect::<Vec<_>>();
+            writeln!(
+                dot,
+                "    index [shape=record, label=\"{{index|{}}}\"];",
+                fields.join("|")
+            )
+            .unwrap();
+
+            for (port, id) in self.index.values().enumerate() {
+                writeln!(dot, "    index:p{} -> n{} [style=dashed];", port, id).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a StructureDump always serializes")
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// The metadata a heap node tracks, one line each
+pub(crate) fn heap_label<H: HeapNodeTrait>(node: &H) -> String {
+    [
+        node.access_count().map(|count| format!("freq {}", count)),
+        node.last_access().map(|tick| format!("last {}", tick)),
+        node.inserted_at().map(|tick| format!("inserted {}", tick)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+// Snapshot of the internal structure, for debugging
+pub trait Dump {
+    fn dump(&self) -> StructureDump;
+}
+
+// A fresh `implementation` replayed up to `operations[step]`, dumped right before and right
+// after it; None if the implementation has no dump()
+pub fn dump_step(
+    implementation: &CacheImplementation,
+    capacity: usize,
+    operations: &[CacheOperation],
+    step: usize,
+) -> Option<[StructureDump; 2]> {
+    let mut cache = (implementation.new)(capacity as i32);
+
+    for operation in &operations[..step] {
+        apply(&mut *cache, operation);
+    }
+    let before = cache.dump()?;
+
+    apply(&mut *cache, &operations[step]);
+    let after = cache.dump()?;
+
+    Some([before, after])
+}
+
+fn apply(cache: &mut dyn LeetCodeCache, operation: &CacheOperation) {
+    match *operation {
+        CacheOperation::Put { key, value } => cache.put(key, value),
+        CacheOperation::Get { key } => {
+            cache.get(key);
+        }
+    }
+}
+
+// dump_step() as `<dir>/<problem>_<name>.{before,after}.{dot,json}`, creating `dir` if needed
+pub fn write_step_dumps(
+    dir: &Path,
+    implementation: &CacheImplementation,
+    capacity: usize,
+    operations: &[CacheOperation],
+    step: usize,
+) -> io::Result<()> {
+    let Some([before, after]) = dump_step(implementation, capacity, operations, step) else {
+        return Ok(());
+    };
+
+    fs::create_dir_all(dir)?;
+
+    let stem = format!("{}_{}", implementation.problem, implementation.name);
+    for (when, dump) in [("before", before), ("after", after)] {
+        fs::write(dir.join(format!("{}.{}.dot", stem, when)), dump.to_dot())?;
+        fs::write(dir.join(format!("{}.{}.json", stem, when)), dump.to_json())?;
+    }
+
+    Ok(())
+}
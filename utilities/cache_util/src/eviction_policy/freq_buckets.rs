@@ -0,0 +1,338 @@
+use super::{EvictionAsStoragePolicy, EvictionPolicy};
+use crate::{Introspect, KeyMetadata, tick};
+
+use std::collections::HashMap;
+
+struct Node<V> {
+    key: i32,
+    value: V,
+    freq: usize,
+    inserted_at: u64, // ticks of the LRU clock
+    last_access: u64,
+
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+#[derive(Default)]
+struct FreqList {
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+// O(1) LFU: one list per frequency, each ordered by last access time (LRU tie-break)
+//
+// As in the classic algorithm, min_freq is only kept exact by touches and inserts: a removal
+// emptying its list leaves it a lower bound, the next insert resetting it to 1. So an evict()
+// or remove() not followed by a put makes the next evict() scan the O(#distinct freqs) lists.
+pub struct EvictionPolicyFreqBuckets<V = ()> {
+    map: HashMap<i32, usize>, // key -> slot's index
+    slots: Vec<Node<V>>,
+    free: Vec<usize>, // indices of vacant slots, reused before growing `slots`
+    freq_map: HashMap<usize, FreqList>, // freq -> list of slots, ordered by last access time
+    min_freq: usize,  // a lower bound of the lists' freqs, exact after a touch or an insert
+}
+
+impl<V> EvictionPolicyFreqBuckets<V> {
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            freq_map: HashMap::new(),
+            min_freq: 1,
+        }
+    }
+
+    fn push_back(&mut self, index: usize) {
+        let freq = self.slots[index].freq;
+        let list = self.freq_map.entry(freq).or_default();
+
+        match list.tail {
+            Some(tail) => {
+                self.slots[tail].next = Some(index);
+            }
+            None => {
+                list.head = Some(index);
+            }
+        }
+
+        self.slots[index].prev = list.tail;
+        self.slots[index].next = None;
+        list.tail = Some(index);
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (freq, prev, next) = {
+            let node = &self.slots[index];
+            (node.freq, node.prev, node.next)
+        };
+
+        if let Some(prev) = prev {
+            self.slots[prev].next = next;
+        }
+        if let Some(next) = next {
+            self.slots[next].prev = prev;
+        }
+
+        if let Some(list) = self.freq_map.get_mut(&freq) {
+            if list.head == Some(index) {
+                list.head = next;
+            }
+            if list.tail == Some(index) {
+                list.tail = prev;
+            }
+
+            if list.head.is_none() {
+                self.freq_map.remove(&freq);
+                if self.min_freq == freq {
+                    self.min_freq += 1;
+                }
+            }
+        }
+    }
+
+    fn touch(&mut self, index: usize) {
+        self.unlink(index);
+        self.slots[index].freq += 1;
+        self.slots[index].last_access = tick();
+        self.push_back(index);
+    }
+
+    fn insert(&mut self, key: i32, value: V) {
+        let now = tick();
+        let node = Node {
+            key,
+            value,
+            freq: 1,
+            inserted_at: now,
+            last_access: now,
+            prev: None,
+            next: None,
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = node;
+                index
+            }
+            None => {
+                self.slots.push(node);
+                self.slots.len() - 1
+            }
+        };
+
+        self.map.insert(key, index);
+        self.min_freq = 1;
+        self.push_back(index);
+    }
+
+    fn pop_lfu(&mut self) -> Option<i32> {
+        if !self.freq_map.contains_key(&self.min_freq) {
+            self.min_freq = *self.freq_map.keys().min()?;
+        }
+
+        let index = self.freq_map[&self.min_freq].head?;
+        Some(self.remove_at(index).key)
+    }
+
+    // the node stays in its vacant slot until insert() reuses it
+    fn remove_at(&mut self, index: usize) -> &Node<V> {
+        self.unlink(index);
+        self.free.push(index);
+
+        let key = self.slots[index].key;
+        self.map.remove(&key);
+
+        &self.slots[index]
+    }
+
+    // Eviction order: lowest freq first, each list from its head
+    fn eviction_order(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut freqs = self.freq_map.keys().copied().collect::<Vec<_>>();
+        freqs.sort_unstable();
+
+        freqs.into_iter().flat_map(move |freq| {
+            std::iter::successors(self.freq_map[&freq].head, |&index| self.slots[index].next)
+        })
+    }
+}
+
+impl<V> Default for EvictionPolicyFreqBuckets<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EvictionPolicy for EvictionPolicyFreqBuckets<()> {
+    fn on_get(&mut self, key: &i32) {
+        if let Some(index) = self.map.get(key).cloned() {
+            self.touch(index);
+        }
+    }
+
+    fn on_put(&mut self, key: i32) {
+        if let Some(index) = self.map.get(&key).cloned() {
+            self.touch(index);
+        } else {
+            self.insert(key, ());
+        }
+    }
+
+    fn evict(&mut self) -> Option<i32> {
+        self.pop_lfu()
+    }
+
+    fn remove(&mut self, key: &i32) {
+        if let Some(index) = self.map.get(key).cloned() {
+            self.remove_at(index);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl EvictionAsStoragePolicy for EvictionPolicyFreqBuckets<i32> {
+    fn evict(&mut self) -> Option<i32> {
+        self.pop_lfu()
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        self.map.get(key).cloned().map(|index| {
+            self.touch(index);
+            self.slots[index].value
+        })
+    }
+
+    fn put(&mut self, key: i32, value: i32) {
+        if let Some(index) = self.map.get(&key).cloned() {
+            // EvictionCache::put() has already touched the key through get()
+            self.slots[index].value = value;
+        } else {
+            self.insert(key, value);
+        }
+    }
+
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        let index = *self.map.get(key)?;
+        Some(self.remove_at(index).value)
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<V> Introspect for EvictionPolicyFreqBuckets<V> {
+    fn inspect(&self, key: &i32) -> Option<KeyMetadata> {
+        let index = *self.map.get(key)?;
+        let node = &self.slots[index];
+
+        Some(KeyMetadata {
+            access_count: Some(node.freq as u64),
+            last_access: Some(node.last_access),
+            inserted_at: Some(node.inserted_at),
+            eviction_rank: self.eviction_order().position(|i| i == index)?,
+        })
+    }
+
+    fn victims(&self, n: usize) -> Vec<i32> {
+        self.eviction_order()
+            .take(n)
+            .map(|index| self.slots[index].key)
+            .collect()
+    }
+}
+
+#[cfg(feature = "validate")]
+impl<V> crate::DebugValidate for EvictionPolicyFreqBuckets<V> {
+    fn debug_validate(&self) {
+        assert_eq!(
+            self.map.len() + self.free.len(),
+            self.slots.len(),
+            "leaked or double-freed slots"
+        );
+
+        let mut listed = 0;
+
+        for (&freq, list) in &self.freq_map {
+            assert!(list.head.is_some(), "empty list for freq {}", freq);
+
+            let mut prev = None;
+            let mut curr = list.head;
+            while let Some(index) = curr {
+                let node = &self.slots[index];
+
+                assert_eq!(node.prev, prev, "prev of {} doesn't match", index);
+                assert_eq!(
+                    node.freq, freq,
+                    "slot {} in the list of freq {}",
+                    index, freq
+                );
+                assert_eq!(
+                    self.map.get(&node.key),
+                    Some(&index),
+                    "map[{}] isn't slot {}",
+                    node.key,
+                    index
+                );
+
+                listed += 1;
+                prev = curr;
+                curr = node.next;
+            }
+
+            assert_eq!(list.tail, prev, "tail of freq {} isn't its last node", freq);
+        }
+
+        assert_eq!(listed, self.map.len(), "keys missing from the lists");
+
+        if let Some(&min_freq) = self.freq_map.keys().min() {
+            assert!(self.min_freq <= min_freq, "min_freq is above the minimum");
+        }
+    }
+}
+
+// one cluster per freq_map list, node ids being slot indices
+#[cfg(feature = "dump")]
+impl<V> crate::Dump for EvictionPolicyFreqBuckets<V> {
+    fn dump(&self) -> crate::StructureDump {
+        let mut dump = crate::StructureDump::new("freq_buckets");
+
+        let mut freqs = self.freq_map.keys().copied().collect::<Vec<_>>();
+        freqs.sort_unstable();
+
+        for freq in freqs {
+            let list =
+                std::iter::successors(self.freq_map[&freq].head, |&index| self.slots[index].next);
+
+            dump.push_list(
+                Some(format!("freq {}", freq)),
+                list.map(|index| {
+                    let node = &self.slots[index];
+                    let label = format!("last {}\ninserted {}", node.last_access, node.inserted_at);
+                    (index, node.key, label)
+                }),
+            );
+        }
+
+        dump.index
+            .extend(self.map.iter().map(|(&key, &index)| (key, index)));
+
+        dump
+    }
+}
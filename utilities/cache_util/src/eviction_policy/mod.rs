@@ -6,3 +6,9 @@ pub use priority_queue::*;
 
 mod vec_hashmap;
 pub use vec_hashmap::*;
+
+mod freq_buckets;
+pub use freq_buckets::*;
+
+mod pairing_heap;
+pub use pairing_heap::*;
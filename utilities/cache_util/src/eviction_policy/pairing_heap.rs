@@ -0,0 +1,383 @@
+use super::{EvictionAsStoragePolicy, EvictionPolicy};
+use crate::introspect::{rank_among, smallest_keys};
+use crate::{HeapNodeTrait, Introspect, KeyMetadata};
+
+use std::collections::HashMap;
+
+struct PairingNode<H> {
+    node: H,
+
+    child: Option<usize>,   // leftmost child
+    sibling: Option<usize>, // next sibling to the right
+    prev: Option<usize>,    // previous sibling, or parent if leftmost child
+}
+
+// Pairing min heap, nodes stored in a Vec and linked by index
+pub struct EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32>,
+{
+    map: HashMap<i32, usize>, // key -> vec's index
+    arr: Vec<PairingNode<H>>,
+    free: Vec<usize>, // indices of vacant slots, reused before growing `arr`
+    root: Option<usize>,
+}
+
+impl<H> EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32>,
+{
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+            arr: Vec::new(),
+            free: Vec::new(),
+            root: None,
+        }
+    }
+
+    // links two trees, the larger root becomes the leftmost child of the smaller one
+    fn meld(&mut self, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        let (a, b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, None) => return a,
+            (None, b) => return b,
+        };
+
+        let (parent, child) = if self.arr[b].node < self.arr[a].node {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        let first_child = self.arr[parent].child;
+        if let Some(first_child) = first_child {
+            self.arr[first_child].prev = Some(child);
+        }
+
+        self.arr[child].sibling = first_child;
+        self.arr[child].prev = Some(parent);
+        self.arr[parent].child = Some(child);
+
+        Some(parent)
+    }
+
+    // classic two-pass pairing: meld pairs left to right, then fold right to left
+    fn merge_pairs(&mut self, first: Option<usize>) -> Option<usize> {
+        let mut pairs = Vec::new();
+
+        let mut curr = first;
+        while let Some(a) = curr {
+            let b = self.arr[a].sibling;
+            curr = b.and_then(|b| self.arr[b].sibling);
+
+            self.detach_links(a);
+            if let Some(b) = b {
+                self.detach_links(b);
+            }
+
+            pairs.push(self.meld(Some(a), b));
+        }
+
+        pairs
+            .into_iter()
+            .rev()
+            .fold(None, |acc, tree| self.meld(tree, acc))
+    }
+
+    fn detach_links(&mut self, index: usize) {
+        self.arr[index].sibling = None;
+        self.arr[index].prev = None;
+    }
+
+    // cuts the subtree rooted at `index` out of its parent's child list
+    fn cut(&mut self, index: usize) {
+        let (prev, sibling) = (self.arr[index].prev, self.arr[index].sibling);
+
+        if let Some(prev) = prev {
+            if self.arr[prev].child == Some(index) {
+                self.arr[prev].child = sibling;
+            } else {
+                self.arr[prev].sibling = sibling;
+            }
+        }
+
+        if let Some(sibling) = sibling {
+            self.arr[sibling].prev = prev;
+        }
+
+        self.detach_links(index);
+    }
+
+    // takes `index` out of the heap, keeping its slot and its node
+    fn detach(&mut self, index: usize) {
+        let children = self.arr[index].child.take();
+        let subtree = self.merge_pairs(children);
+
+        if self.root == Some(index) {
+            self.root = subtree;
+        } else {
+            self.cut(index);
+            self.root = self.meld(self.root, subtree);
+        }
+    }
+
+    fn attach(&mut self, index: usize) {
+        self.root = self.meld(self.root, Some(index));
+    }
+
+    fn on_access_at(&mut self, index: usize) {
+        self.arr[index].node.on_access();
+
+        // priorities only increase on access, so re-insert instead of a decrease-key
+        self.detach(index);
+        self.attach(index);
+    }
+
+    fn insert(&mut self, node: H) {
+        let key = *node.key();
+        let node = PairingNode {
+            node,
+            child: None,
+            sibling: None,
+            prev: None,
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.arr[index] = node;
+                index
+            }
+            None => {
+                self.arr.push(node);
+                self.arr.len() - 1
+            }
+        };
+
+        self.map.insert(key, index);
+        self.attach(index);
+    }
+
+    fn pop(&mut self) -> Option<i32> {
+        let index = self.root?;
+        Some(*self.remove_at(index).key())
+    }
+
+    // the node stays in its vacant slot until insert() reuses it
+    fn remove_at(&mut self, index: usize) -> &H {
+        self.detach(index);
+        self.free.push(index);
+
+        let node = &self.arr[index].node;
+        self.map.remove(node.key());
+
+        node
+    }
+}
+
+impl<H> Default for EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> EvictionPolicy for EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32, Value = ()>,
+{
+    fn on_get(&mut self, key: &i32) {
+        if let Some(index) = self.map.get(key).cloned() {
+            self.on_access_at(index);
+        }
+    }
+
+    fn on_put(&mut self, key: i32) {
+        if let Some(index) = self.map.get(&key).cloned() {
+            self.on_access_at(index);
+        } else {
+            self.insert(HeapNodeTrait::new(key, ()));
+        }
+    }
+
+    fn evict(&mut self) -> Option<i32> {
+        self.pop()
+    }
+
+    fn remove(&mut self, key: &i32) {
+        if let Some(index) = self.map.get(key).cloned() {
+            self.remove_at(index);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<H> EvictionAsStoragePolicy for EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32, Value = i32>,
+{
+    fn evict(&mut self) -> Option<i32> {
+        self.pop()
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        self.map.get(key).cloned().map(|index| {
+            let val = *self.arr[index].node.value();
+            self.on_access_at(index);
+            val
+        })
+    }
+
+    fn put(&mut self, key: i32, value: i32) {
+        if let Some(index) = self.map.get(&key).cloned() {
+            // EvictionCache::put() has already accessed the key through get()
+            self.arr[index].node.set_value(value);
+        } else {
+            self.insert(HeapNodeTrait::new(key, value));
+        }
+    }
+
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        let index = *self.map.get(key)?;
+        Some(*self.remove_at(index).value())
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.map.contains_key(key)
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<H> Introspect for EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32>,
+{
+    fn inspect(&self, key: &i32) -> Option<KeyMetadata> {
+        let node = &self.arr[*self.map.get(key)?].node;
+        let rank = rank_among(node, self.map.values().map(|&index| &self.arr[index].node));
+
+        Some(KeyMetadata::from_node(node, rank))
+    }
+
+    fn victims(&self, n: usize) -> Vec<i32> {
+        let entries = self
+            .map
+            .iter()
+            .map(|(&key, &index)| (key, &self.arr[index].node));
+
+        smallest_keys(entries, n)
+    }
+}
+
+#[cfg(feature = "validate")]
+impl<H> crate::DebugValidate for EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32>,
+{
+    fn debug_validate(&self) {
+        assert_eq!(
+            self.map.len() + self.free.len(),
+            self.arr.len(),
+            "leaked or double-freed slots"
+        );
+
+        for (key, &index) in &self.map {
+            assert_eq!(
+                self.arr[index].node.key(),
+                key,
+                "map[{}] isn't slot {}",
+                key,
+                index
+            );
+        }
+
+        let mut visited = 0;
+
+        if let Some(root) = self.root {
+            assert_eq!(self.arr[root].prev, None, "root has a parent");
+            assert_eq!(self.arr[root].sibling, None, "root has a sibling");
+        }
+
+        // every child is no smaller than its parent, prev links mirror child/sibling links
+        let mut stack = self.root.into_iter().collect::<Vec<_>>();
+        while let Some(parent) = stack.pop() {
+            visited += 1;
+
+            let mut prev = parent;
+            let mut curr = self.arr[parent].child;
+            while let Some(child) = curr {
+                assert_eq!(
+                    self.arr[child].prev,
+                    Some(prev),
+                    "prev of {} isn't {}",
+                    child,
+                    prev
+                );
+                assert!(
+                    self.arr[parent].node <= self.arr[child].node,
+                    "heap order broken between {} and {}",
+                    parent,
+                    child
+                );
+
+                stack.push(child);
+                prev = child;
+                curr = self.arr[child].sibling;
+            }
+        }
+
+        assert_eq!(visited, self.map.len(), "nodes unreachable from the root");
+    }
+}
+
+// every tree with its child/sibling links, node ids being `arr` indices
+#[cfg(feature = "dump")]
+impl<H> crate::Dump for EvictionPolicyPH<H>
+where
+    H: HeapNodeTrait<Key = i32>,
+{
+    fn dump(&self) -> crate::StructureDump {
+        let mut dump = crate::StructureDump::new("pairing_heap");
+
+        let mut indices = self.map.values().copied().collect::<Vec<_>>();
+        indices.sort_unstable();
+
+        for index in indices {
+            let PairingNode {
+                node,
+                child,
+                sibling,
+                ..
+            } = &self.arr[index];
+
+            dump.push_node(index, *node.key(), crate::heap_label(node), None);
+            if let Some(child) = *child {
+                dump.push_edge(index, child, "child");
+            }
+            if let Some(sibling) = *sibling {
+                dump.push_edge(index, sibling, "sibling");
+            }
+        }
+
+        dump.index
+            .extend(self.map.iter().map(|(&key, &index)| (key, index)));
+
+        dump
+    }
+}
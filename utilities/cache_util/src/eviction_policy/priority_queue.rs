@@ -1,5 +1,6 @@
 use super::{EvictionAsStoragePolicy, EvictionPolicy};
-use crate::HeapNodeTrait;
+use crate::introspect::{rank_among, smallest_keys};
+use crate::{HeapNodeTrait, Introspect, KeyMetadata};
 
 use priority_queue::PriorityQueue;
 use std::cmp::Reverse;
@@ -52,6 +53,18 @@ where
     fn evict(&mut self) -> Option<i32> {
         self.pq.pop().map(|(key, _)| key)
     }
+
+    fn remove(&mut self, key: &i32) {
+        self.pq.remove(key);
+    }
+
+    fn len(&self) -> usize {
+        self.pq.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
 }
 
 impl<H> EvictionAsStoragePolicy for EvictionPolicyPQ<H>
@@ -73,13 +86,22 @@ where
     }
 
     fn put(&mut self, key: i32, value: i32) {
+        // EvictionCache::put() has already accessed the key through get()
         if !self.pq.change_priority_by(&key, |p| {
-            p.0.on_access();
+            p.0.set_value(value);
         }) {
             self.pq.push(key, Reverse(HeapNodeTrait::new((), value)));
         }
     }
 
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        self.pq.remove(key).map(|(_, priority)| *priority.0.value())
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.pq.get(key).is_some()
+    }
+
     fn len(&self) -> usize {
         self.pq.len()
     }
@@ -88,3 +110,56 @@ where
         self.pq.is_empty()
     }
 }
+
+// pop() returns the greatest Reverse, i.e. the smallest node
+impl<H> Introspect for EvictionPolicyPQ<H>
+where
+    H: HeapNodeTrait<Key = ()>,
+{
+    fn inspect(&self, key: &i32) -> Option<KeyMetadata> {
+        let node = &self.pq.get_priority(key)?.0;
+        let rank = rank_among(node, self.pq.iter().map(|(_, priority)| &priority.0));
+
+        Some(KeyMetadata::from_node(node, rank))
+    }
+
+    fn victims(&self, n: usize) -> Vec<i32> {
+        smallest_keys(self.pq.iter().map(|(&key, priority)| (key, &priority.0)), n)
+    }
+}
+
+// PriorityQueue keeps its own heap and index consistent, GenericCache checks the size
+#[cfg(feature = "validate")]
+impl<H> crate::DebugValidate for EvictionPolicyPQ<H>
+where
+    H: HeapNodeTrait<Key = ()>,
+{
+    fn debug_validate(&self) {}
+}
+
+// PriorityQueue keeps its heap private, so the entries are listed in eviction order instead,
+// node ids being eviction ranks
+#[cfg(feature = "dump")]
+impl<H> crate::Dump for EvictionPolicyPQ<H>
+where
+    H: HeapNodeTrait<Key = ()>,
+{
+    fn dump(&self) -> crate::StructureDump {
+        let mut dump = crate::StructureDump::new("priority_queue");
+
+        let victims = self.victims(self.pq.len());
+        dump.push_list(
+            None,
+            victims.iter().enumerate().map(|(rank, &key)| {
+                let node = &self.pq.get_priority(&key).expect("victims are queued").0;
+                (rank, key, crate::heap_label(node))
+            }),
+        );
+
+        for (rank, key) in victims.into_iter().enumerate() {
+            dump.index.insert(key, rank);
+        }
+
+        dump
+    }
+}
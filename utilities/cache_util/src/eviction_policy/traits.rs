@@ -1,7 +1,23 @@
 pub trait EvictionPolicy {
     fn on_get(&mut self, key: &i32);
+    // on_get() for each key in order; callers only pass keys that are cached
+    fn on_get_all(&mut self, keys: &[i32]) {
+        for key in keys {
+            self.on_get(key);
+        }
+    }
     fn on_put(&mut self, key: i32);
+    // on_put() for each key in order, with no eviction in between
+    fn on_put_all(&mut self, keys: &[i32]) {
+        for &key in keys {
+            self.on_put(key);
+        }
+    }
     fn evict(&mut self) -> Option<i32>;
+    // forgets the key, if tracked, as if it had never been put
+    fn remove(&mut self, key: &i32);
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool;
 }
 
 pub trait EvictionAsStoragePolicy {
@@ -14,10 +30,31 @@ pub trait EvictionAsStoragePolicy {
     fn evict(&mut self) -> Option<i32>;
 
     fn put(&mut self, _key: i32, value: i32);
+    // What EvictionCache::put() does for each item in order, with no eviction in between:
+    // its lookup through get() counts as an access, then put() and on_put()
+    fn put_all(&mut self, items: &[(i32, i32)]) {
+        for &(key, value) in items {
+            self.get(&key);
+            self.put(key, value);
+            self.on_put(key);
+        }
+    }
     fn get(&mut self, key: &i32) -> Option<i32>;
-    fn remove(&mut self, _key: &i32) {
-        /* NO OP */
+    // What EvictionCache::get() does for each key in order: get(), then on_get() on a hit
+    fn get_all(&mut self, keys: &[i32]) -> Vec<Option<i32>> {
+        keys.iter()
+            .map(|key| {
+                let value = self.get(key);
+                if value.is_some() {
+                    self.on_get(key);
+                }
+                value
+            })
+            .collect()
     }
+    // also what EvictionCache::put() calls on the key evict() returned, which is already gone
+    fn remove(&mut self, key: &i32) -> Option<i32>;
+    fn contains(&self, key: &i32) -> bool; // without counting as an access
     fn len(&self) -> usize;
     fn is_empty(&self) -> bool;
 }
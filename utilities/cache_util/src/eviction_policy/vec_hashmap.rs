@@ -1,217 +1,336 @@
 use super::{EvictionAsStoragePolicy, EvictionPolicy};
-use crate::HeapNodeTrait;
+use crate::introspect::{rank_among, smallest_keys};
+use crate::{HeapNodeTrait, Introspect, KeyMetadata};
 
 use std::collections::HashMap;
 
-pub struct EvictionPolicyVHM<H>
+// D-ary min heap, D = 2 is the classic binary heap
+//
+// Struct-of-arrays layout: the heap only moves the compact priorities in `nodes`
+// and their slot ids in `slots`. Keys live in `keys`, indexed by slot, so a swap
+// updates `positions` by index instead of looking keys up in `map`.
+pub struct EvictionPolicyVHM<H, const D: usize = 2>
 where
-    H: HeapNodeTrait<Key = i32>,
+    H: HeapNodeTrait<Key = ()>,
 {
-    map: HashMap<i32, usize>, // key -> vec's index
-    arr: Vec<H>,
+    map: HashMap<i32, usize>, // key -> slot
+    nodes: Vec<H>,            // heap of priorities
+    slots: Vec<usize>,        // parallel to `nodes`: heap index -> slot
+    keys: Vec<i32>,           // slot -> key
+    positions: Vec<usize>,    // slot -> heap index
+    free: Vec<usize>,         // vacant slots, reused before growing `keys`
 }
 
-impl<H> EvictionPolicyVHM<H>
+impl<H, const D: usize> EvictionPolicyVHM<H, D>
 where
-    H: HeapNodeTrait<Key = i32>,
+    H: HeapNodeTrait<Key = ()>,
 {
     pub fn new() -> Self {
+        const { assert!(D >= 2, "heap arity must be at least 2") };
+
         Self {
             map: HashMap::new(),
-            arr: Vec::new(),
+            nodes: Vec::new(),
+            slots: Vec::new(),
+            keys: Vec::new(),
+            positions: Vec::new(),
+            free: Vec::new(),
         }
     }
 
-    fn get_left_child(&self, mut index: usize) -> Option<usize> {
-        index = (index << 1) + 1;
-        if index < self.arr.len() {
+    fn get_first_child(&self, mut index: usize) -> Option<usize> {
+        index = index * D + 1;
+        if index < self.nodes.len() {
             return Some(index);
         }
 
         None
     }
 
-    fn swap_nodes_with_key(&mut self, key1: &i32, index1: usize, key2: &i32, index2: usize) {
-        if let [Some(mut_index1), Some(mut_index2)] = self.map.get_many_mut([key1, key2]) {
-            // self.arr.swap(*mut_index1, *mut_index2); // slower
-            self.arr.swap(index1, index2);
-
-            //swap(mut_index1, mut_index2); // same as below
-            *mut_index1 = index2;
-            *mut_index2 = index1;
-        }
-    }
-
     fn swap_nodes(&mut self, index1: usize, index2: usize) {
-        debug_assert!(index1 < self.arr.len());
-        debug_assert!(index2 < self.arr.len());
+        debug_assert!(index1 < self.nodes.len());
+        debug_assert!(index2 < self.nodes.len());
 
-        if index1 == index2 {
-            return;
-        }
+        self.nodes.swap(index1, index2);
+        self.slots.swap(index1, index2);
 
-        let (key1, key2) = (*self.arr[index1].key(), *self.arr[index2].key());
-        self.swap_nodes_with_key(&key1, index1, &key2, index2);
+        self.positions[self.slots[index1]] = index1;
+        self.positions[self.slots[index2]] = index2;
     }
 
     fn sift_up(&mut self, mut index: usize) {
-        debug_assert!(index < self.arr.len());
+        debug_assert!(index < self.nodes.len());
 
         while index > 0 {
-            let parent_index = (index - 1) >> 1;
-
-            let (node, parent_node) = (&self.arr[index], &self.arr[parent_index]);
+            let parent_index = (index - 1) / D;
 
             // already in order
-            if *node >= *parent_node {
+            if self.nodes[index] >= self.nodes[parent_index] {
                 break;
             }
 
-            let (key, parent_key) = (*node.key(), *parent_node.key());
-            self.swap_nodes_with_key(&key, index, &parent_key, parent_index);
+            self.swap_nodes(index, parent_index);
 
             index = parent_index;
         }
     }
 
     fn sift_down(&mut self, mut index: usize) {
-        debug_assert!(index < self.arr.len());
-
-        while let Some(left_child_index) = self.get_left_child(index) {
-            let node = &self.arr[index];
+        debug_assert!(index < self.nodes.len());
 
-            let (next_index, next_node) = self.pick_smaller_child(left_child_index);
+        while let Some(first_child_index) = self.get_first_child(index) {
+            let next_index = self.pick_smallest_child(first_child_index);
 
             // already in order
-            if *node <= *next_node {
+            if self.nodes[index] <= self.nodes[next_index] {
                 break;
             }
 
-            let (key, next_key) = (*node.key(), *next_node.key());
-            self.swap_nodes_with_key(&key, index, &next_key, next_index);
+            self.swap_nodes(index, next_index);
 
             index = next_index;
         }
     }
 
-    fn pick_smaller_child(&self, left_child_index: usize) -> (usize, &H) {
-        let left_child_node = &self.arr[left_child_index];
+    fn pick_smallest_child(&self, first_child_index: usize) -> usize {
+        let end_index = (first_child_index + D).min(self.nodes.len());
 
-        let right_child_index = left_child_index + 1;
-        if right_child_index < self.arr.len() {
-            let right_child_node = &self.arr[right_child_index];
-
-            // right child is strictly smaller
-            if *left_child_node > *right_child_node {
-                return (right_child_index, right_child_node);
+        let mut smallest_index = first_child_index;
+        for child_index in (first_child_index + 1)..end_index {
+            // later child is strictly smaller
+            if self.nodes[smallest_index] > self.nodes[child_index] {
+                smallest_index = child_index;
             }
         }
 
-        (left_child_index, left_child_node)
+        smallest_index
     }
-}
 
-impl<H> Default for EvictionPolicyVHM<H>
-where
-    H: HeapNodeTrait<Key = i32>,
-{
-    fn default() -> Self {
-        Self::new()
-    }
-}
+    // Floyd's bottom-up construction: sift down every parent, the last one first, O(n)
+    fn heapify(&mut self) {
+        if self.nodes.len() < 2 {
+            return;
+        }
 
-impl<H> EvictionPolicy for EvictionPolicyVHM<H>
-where
-    H: HeapNodeTrait<Key = i32, Value = ()>,
-{
-    fn on_get(&mut self, key: &i32) {
-        if let Some(index) = self.map.get(key).cloned() {
-            self.arr[index].on_access();
+        let last_parent_index = (self.nodes.len() - 2) / D;
+        for index in (0..=last_parent_index).rev() {
             self.sift_down(index);
         }
     }
 
-    fn on_put(&mut self, key: i32) {
-        if let Some(index) = self.map.get(&key) {
-            self.sift_down(*index);
-        } else {
-            self.arr.push(HeapNodeTrait::new(key, ()));
-
-            let index = self.arr.len() - 1;
-            self.map.insert(key, index);
-            self.sift_up(index);
-        }
+    // A batch at least as large as the heap is cheaper to append and heapify once than
+    // to sift up one key at a time
+    fn should_heapify(&self, batch_len: usize) -> bool {
+        batch_len >= self.nodes.len()
     }
 
-    fn evict(&mut self) -> Option<i32> {
-        /*         if let Some(node) = self.arr.swap_remove_back(0) {
-            self.map.remove(node.key());
-
-            if self.arr.is_empty() {
-                return None;
+    // Applies a batch in order with every sift deferred: `access` updates the node of a key
+    // already present (or added earlier in the batch), `create` makes the node of a new one.
+    // Heap order is restored once at the end, by a rebuild for a large batch, otherwise by
+    // sifting down each accessed node, the deepest first (on_access() only ever raises a
+    // node, so its subtree is all that can be out of order), then pushing the new ones.
+    fn apply_batch<T: Copy>(
+        &mut self,
+        items: &[(i32, T)],
+        access: impl Fn(&mut H, T),
+        create: impl Fn(T) -> H,
+    ) {
+        if self.should_heapify(items.len()) {
+            for &(key, arg) in items {
+                match self.map.get(&key).cloned() {
+                    Some(slot) => access(&mut self.nodes[self.positions[slot]], arg),
+                    None => self.push_unordered(key, create(arg)),
+                }
             }
 
-            let key = *self.arr[0].key();
-            if let Some(index) = self.map.get_mut(&key) {
-                *index = 0;
+            self.heapify();
+            return;
+        }
+
+        let mut accessed = Vec::new();
+        let mut pending: Vec<(i32, H)> = Vec::new();
+        let mut pending_indices: HashMap<i32, usize> = HashMap::new(); // key -> index in `pending`
+
+        for &(key, arg) in items {
+            if let Some(slot) = self.map.get(&key).cloned() {
+                let index = self.positions[slot];
+                access(&mut self.nodes[index], arg);
+                accessed.push(index);
+            } else if let Some(&pending_index) = pending_indices.get(&key) {
+                access(&mut pending[pending_index].1, arg);
+            } else {
+                pending_indices.insert(key, pending.len());
+                pending.push((key, create(arg)));
             }
+        }
 
-            self.sift_down(0);
+        // sift_down(index) only moves nodes below index, so the shallower ones stay put
+        accessed.sort_unstable_by(|a, b| b.cmp(a));
+        accessed.dedup();
+        for index in accessed {
+            self.sift_down(index);
+        }
 
-            return Some(*node.key());
+        for (key, node) in pending {
+            self.push(key, node);
         }
+    }
+
+    fn push(&mut self, key: i32, node: H) {
+        self.push_unordered(key, node);
+        self.sift_up(self.nodes.len() - 1);
+    }
+
+    // push() leaving heap order to a later heapify()
+    fn push_unordered(&mut self, key: i32, node: H) {
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.keys[slot] = key;
+                slot
+            }
+            None => {
+                self.keys.push(key);
+                self.positions.push(0);
+                self.keys.len() - 1
+            }
+        };
 
-        None */
+        let index = self.nodes.len();
+        self.nodes.push(node);
+        self.slots.push(slot);
+        self.positions[slot] = index;
 
-        if self.arr.is_empty() {
+        self.map.insert(key, slot);
+    }
+
+    fn pop(&mut self) -> Option<i32> {
+        if self.nodes.is_empty() {
             return None;
         }
 
-        let last_index = self.arr.len() - 1;
+        Some(self.remove_at(0).0)
+    }
+
+    fn remove_key(&mut self, key: &i32) -> Option<H> {
+        let slot = *self.map.get(key)?;
+        Some(self.remove_at(self.positions[slot]).1)
+    }
+
+    // the last node takes the place of the removed one, then moves whichever way it is out of order
+    fn remove_at(&mut self, index: usize) -> (i32, H) {
+        debug_assert!(index < self.nodes.len());
 
-        if last_index > 0 {
-            self.swap_nodes(0, last_index);
+        let last_index = self.nodes.len() - 1;
+
+        if index != last_index {
+            self.swap_nodes(index, last_index);
         }
 
-        self.map.remove(self.arr[last_index].key());
-        let result = self.arr.pop().map(|node| *node.key());
+        let node = self.nodes.pop().expect("index is in the heap");
+        let slot = self.slots.pop().expect("parallel to `nodes`");
+
+        let key = self.keys[slot];
+        self.map.remove(&key);
+        self.free.push(slot);
 
-        if last_index > 0 {
-            self.sift_down(0);
+        if index != last_index {
+            self.sift_down(index);
+            self.sift_up(index);
         }
 
-        result
+        (key, node)
     }
 }
 
-impl<H> EvictionAsStoragePolicy for EvictionPolicyVHM<H>
+impl<H, const D: usize> Default for EvictionPolicyVHM<H, D>
 where
-    H: HeapNodeTrait<Key = i32, Value = i32>,
+    H: HeapNodeTrait<Key = ()>,
 {
-    fn evict(&mut self) -> Option<i32> {
-        if self.arr.is_empty() {
-            return None;
-        }
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        let last_index = self.arr.len() - 1;
+impl<H, const D: usize> EvictionPolicy for EvictionPolicyVHM<H, D>
+where
+    H: HeapNodeTrait<Key = (), Value = ()>,
+{
+    fn on_get(&mut self, key: &i32) {
+        if let Some(slot) = self.map.get(key).cloned() {
+            let index = self.positions[slot];
 
-        if last_index > 0 {
-            self.swap_nodes(0, last_index);
+            self.nodes[index].on_access();
+            self.sift_down(index);
         }
+    }
 
-        self.map.remove(self.arr[last_index].key());
-        let result = self.arr.pop().map(|node| *node.key());
+    fn on_put(&mut self, key: i32) {
+        if let Some(slot) = self.map.get(&key).cloned() {
+            let index = self.positions[slot];
 
-        if last_index > 0 {
-            self.sift_down(0);
+            // updating an existing key counts as an access
+            self.nodes[index].on_access();
+            self.sift_down(index);
+        } else {
+            self.push(key, HeapNodeTrait::new((), ()));
         }
+    }
+
+    fn on_put_all(&mut self, keys: &[i32]) {
+        let items = keys.iter().map(|&key| (key, ())).collect::<Vec<_>>();
+
+        // updating an existing key counts as an access, as in on_put()
+        self.apply_batch(
+            &items,
+            |node, ()| node.on_access(),
+            |()| HeapNodeTrait::new((), ()),
+        );
+    }
+
+    fn on_get_all(&mut self, keys: &[i32]) {
+        let hits = keys
+            .iter()
+            .filter(|key| self.map.contains_key(key))
+            .map(|&key| (key, ()))
+            .collect::<Vec<_>>();
+
+        self.apply_batch(
+            &hits,
+            |node, ()| node.on_access(),
+            |()| unreachable!("only keys in the heap are accessed"),
+        );
+    }
+
+    fn evict(&mut self) -> Option<i32> {
+        self.pop()
+    }
+
+    fn remove(&mut self, key: &i32) {
+        self.remove_key(key);
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
 
-        result
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<H, const D: usize> EvictionAsStoragePolicy for EvictionPolicyVHM<H, D>
+where
+    H: HeapNodeTrait<Key = (), Value = i32>,
+{
+    fn evict(&mut self) -> Option<i32> {
+        self.pop()
     }
 
     fn get(&mut self, key: &i32) -> Option<i32> {
-        self.map.get(key).cloned().map(|index| {
-            let node = &mut self.arr[index];
+        self.map.get(key).cloned().map(|slot| {
+            let index = self.positions[slot];
+
+            let node = &mut self.nodes[index];
             let val = *node.value();
 
             node.on_access();
@@ -222,18 +341,60 @@ where
     }
 
     fn put(&mut self, key: i32, value: i32) {
-        if let Some(index) = self.map.get(&key).cloned() {
-            self.arr[index].set_value(value);
+        if let Some(slot) = self.map.get(&key).cloned() {
+            let index = self.positions[slot];
+
+            self.nodes[index].set_value(value);
             self.sift_down(index);
         } else {
-            self.arr.push(HeapNodeTrait::new(key, value));
-
-            let index = self.arr.len() - 1;
-            self.map.insert(key, index);
-            self.sift_up(index);
+            self.push(key, HeapNodeTrait::new((), value));
         }
     }
 
+    // get() then put() for each item, as EvictionCache::put() does
+    fn put_all(&mut self, items: &[(i32, i32)]) {
+        self.apply_batch(
+            items,
+            |node, value| {
+                node.on_access();
+                node.set_value(value);
+            },
+            |value| HeapNodeTrait::new((), value),
+        );
+    }
+
+    fn get_all(&mut self, keys: &[i32]) -> Vec<Option<i32>> {
+        let values = keys
+            .iter()
+            .map(|key| {
+                let slot = *self.map.get(key)?;
+                Some(*self.nodes[self.positions[slot]].value())
+            })
+            .collect::<Vec<_>>();
+
+        let hits = keys
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_some())
+            .map(|(&key, _)| (key, ()))
+            .collect::<Vec<_>>();
+        self.apply_batch(
+            &hits,
+            |node, ()| node.on_access(),
+            |()| unreachable!("only keys in the heap are accessed"),
+        );
+
+        values
+    }
+
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        self.remove_key(key).map(|node| *node.value())
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.map.contains_key(key)
+    }
+
     fn len(&self) -> usize {
         self.map.len()
     }
@@ -242,3 +403,103 @@ where
         self.map.is_empty()
     }
 }
+
+impl<H, const D: usize> Introspect for EvictionPolicyVHM<H, D>
+where
+    H: HeapNodeTrait<Key = ()>,
+{
+    fn inspect(&self, key: &i32) -> Option<KeyMetadata> {
+        let node = &self.nodes[self.positions[*self.map.get(key)?]];
+
+        Some(KeyMetadata::from_node(
+            node,
+            rank_among(node, self.nodes.iter()),
+        ))
+    }
+
+    fn victims(&self, n: usize) -> Vec<i32> {
+        let entries = self
+            .slots
+            .iter()
+            .zip(&self.nodes)
+            .map(|(&slot, node)| (self.keys[slot], node));
+
+        smallest_keys(entries, n)
+    }
+}
+
+#[cfg(feature = "validate")]
+impl<H, const D: usize> crate::DebugValidate for EvictionPolicyVHM<H, D>
+where
+    H: HeapNodeTrait<Key = ()>,
+{
+    fn debug_validate(&self) {
+        let len = self.nodes.len();
+
+        assert_eq!(self.slots.len(), len, "slots out of sync with nodes");
+        assert_eq!(self.map.len(), len, "map out of sync with nodes");
+        assert_eq!(
+            self.keys.len(),
+            self.positions.len(),
+            "keys out of sync with positions"
+        );
+        assert_eq!(
+            len + self.free.len(),
+            self.keys.len(),
+            "leaked or double-freed slots"
+        );
+
+        for (index, &slot) in self.slots.iter().enumerate() {
+            assert_eq!(
+                self.positions[slot], index,
+                "slot {} at heap index {}",
+                slot, index
+            );
+
+            let key = self.keys[slot];
+            assert_eq!(
+                self.map.get(&key),
+                Some(&slot),
+                "map[{}] isn't slot {}",
+                key,
+                slot
+            );
+
+            if index > 0 {
+                let parent = (index - 1) / D;
+                assert!(
+                    self.nodes[parent] <= self.nodes[index],
+                    "heap order broken between {} and {}",
+                    parent,
+                    index
+                );
+            }
+        }
+    }
+}
+
+// the heap array as a tree, node ids being heap indices
+#[cfg(feature = "dump")]
+impl<H, const D: usize> crate::Dump for EvictionPolicyVHM<H, D>
+where
+    H: HeapNodeTrait<Key = ()>,
+{
+    fn dump(&self) -> crate::StructureDump {
+        let mut dump = crate::StructureDump::new("d_ary_heap");
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let key = self.keys[self.slots[index]];
+            dump.push_node(index, key, crate::heap_label(node), None);
+
+            for child in index * D + 1..(index * D + D + 1).min(self.nodes.len()) {
+                dump.push_edge(index, child, "child");
+            }
+        }
+
+        for (&key, &slot) in &self.map {
+            dump.index.insert(key, self.positions[slot]);
+        }
+
+        dump
+    }
+}
@@ -1,6 +1,9 @@
-use crate::Cache;
 use crate::CacheStorage;
+use crate::{Cache, RemovableCache, SizedCache};
 use crate::{EvictionAsStoragePolicy, EvictionPolicy};
+use crate::{Introspect, KeyMetadata};
+
+use std::collections::HashSet;
 
 pub struct GenericCache<P: EvictionPolicy, S: CacheStorage> {
     policy: P,
@@ -17,10 +20,52 @@ impl<P: EvictionPolicy, S: CacheStorage> GenericCache<P, S> {
         }
     }
 
+    // new() then extend()
+    pub fn from_iter(
+        policy: P,
+        storage: S,
+        capacity: usize,
+        items: impl IntoIterator<Item = (i32, i32)>,
+    ) -> Self {
+        let mut cache = Self::new(policy, storage, capacity);
+        cache.extend(items);
+        cache
+    }
+
     pub fn is_full(&self) -> bool {
         self.storage.len() == self.capacity
     }
+
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    pub fn contains(&self, key: &i32) -> bool {
+        self.storage.contains(key)
+    }
+
+    pub fn remove(&mut self, key: &i32) -> Option<i32> {
+        let value = self.storage.remove(key)?;
+        self.policy.remove(key);
+        Some(value)
+    }
 }
+
+impl<P: EvictionPolicy + Introspect, S: CacheStorage> GenericCache<P, S> {
+    pub fn inspect(&self, key: &i32) -> Option<KeyMetadata> {
+        self.policy.inspect(key)
+    }
+
+    // the next `n` keys put() would evict, without evicting them
+    pub fn victims(&self, n: usize) -> Vec<i32> {
+        self.policy.victims(n)
+    }
+}
+
 pub struct EvictionCache<P: EvictionAsStoragePolicy> {
     policy: P,
     capacity: usize,
@@ -31,14 +76,56 @@ impl<P: EvictionAsStoragePolicy> EvictionCache<P> {
         Self { policy, capacity }
     }
 
+    // new() then extend()
+    pub fn from_iter(
+        policy: P,
+        capacity: usize,
+        items: impl IntoIterator<Item = (i32, i32)>,
+    ) -> Self {
+        let mut cache = Self::new(policy, capacity);
+        cache.extend(items);
+        cache
+    }
+
     pub fn is_full(&self) -> bool {
         self.policy.len() == self.capacity // hack
     }
+
+    pub fn len(&self) -> usize {
+        self.policy.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policy.is_empty()
+    }
+
+    pub fn contains(&self, key: &i32) -> bool {
+        self.policy.contains(key)
+    }
+
+    pub fn remove(&mut self, key: &i32) -> Option<i32> {
+        self.policy.remove(key)
+    }
+}
+
+impl<P: EvictionAsStoragePolicy + Introspect> EvictionCache<P> {
+    pub fn inspect(&self, key: &i32) -> Option<KeyMetadata> {
+        self.policy.inspect(key)
+    }
+
+    // the next `n` keys put() would evict, without evicting them
+    pub fn victims(&self, n: usize) -> Vec<i32> {
+        self.policy.victims(n)
+    }
 }
 
 macro_rules! GenericCacheImpl {
     ($policy:ident, $storage:ident) => {
         fn put(&mut self, key: i32, value: i32) {
+            if self.capacity == 0 {
+                return;
+            }
+
             if self.$storage.get(&key).is_none() && self.is_full() {
                 self.$policy.evict().inspect(|evicted_key| {
                     self.$storage.remove(evicted_key);
@@ -56,10 +143,180 @@ macro_rules! GenericCacheImpl {
     };
 }
 
+// How many leading items go in without an eviction, i.e. add at most `free` new keys
+fn fitting_prefix(items: &[(i32, i32)], free: usize, contains: impl Fn(&i32) -> bool) -> usize {
+    let mut new_keys = HashSet::new();
+
+    for (index, (key, _)) in items.iter().enumerate() {
+        if !contains(key) && new_keys.insert(*key) && new_keys.len() > free {
+            return index;
+        }
+    }
+
+    items.len()
+}
+
 impl<P: EvictionPolicy, S: CacheStorage> Cache for GenericCache<P, S> {
     GenericCacheImpl!(policy, storage);
+
+    // the items that fit go to the policy as one batch, one that has to evict goes through
+    // put(), then the next batch starts
+    fn put_many<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = (i32, i32)>,
+    {
+        let items = items.into_iter().collect::<Vec<_>>();
+
+        let mut rest = items.as_slice();
+        while let Some(&(key, value)) = rest.first() {
+            let free = self.capacity.saturating_sub(self.storage.len());
+            let fitting = fitting_prefix(rest, free, |key| self.storage.contains(key));
+            if fitting == 0 {
+                self.put(key, value);
+                rest = &rest[1..];
+                continue;
+            }
+
+            let (batch, remaining) = rest.split_at(fitting);
+            for &(key, value) in batch {
+                self.storage.put(key, value);
+            }
+            let keys = batch.iter().map(|&(key, _)| key).collect::<Vec<_>>();
+            self.policy.on_put_all(&keys);
+
+            rest = remaining;
+        }
+    }
+
+    fn get_many(&mut self, keys: &[i32]) -> Vec<Option<i32>> {
+        let values = keys
+            .iter()
+            .map(|key| self.storage.get(key))
+            .collect::<Vec<_>>();
+
+        let hits = keys
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_some())
+            .map(|(&key, _)| key)
+            .collect::<Vec<_>>();
+        self.policy.on_get_all(&hits);
+
+        values
+    }
 }
 
 impl<P: EvictionAsStoragePolicy> Cache for EvictionCache<P> {
     GenericCacheImpl!(policy, policy); // hack
+
+    fn put_many<I>(&mut self, items: I)
+    where
+        I: IntoIterator<Item = (i32, i32)>,
+    {
+        let items = items.into_iter().collect::<Vec<_>>();
+
+        let mut rest = items.as_slice();
+        while let Some(&(key, value)) = rest.first() {
+            let free = self.capacity.saturating_sub(self.policy.len());
+            let fitting = fitting_prefix(rest, free, |key| self.policy.contains(key));
+            if fitting == 0 {
+                self.put(key, value);
+                rest = &rest[1..];
+                continue;
+            }
+
+            let (batch, remaining) = rest.split_at(fitting);
+            self.policy.put_all(batch);
+
+            rest = remaining;
+        }
+    }
+
+    fn get_many(&mut self, keys: &[i32]) -> Vec<Option<i32>> {
+        self.policy.get_all(keys)
+    }
+}
+
+impl<P: EvictionPolicy, S: CacheStorage> SizedCache for GenericCache<P, S> {
+    fn len(&self) -> usize {
+        GenericCache::len(self)
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        GenericCache::contains(self, key)
+    }
+}
+
+impl<P: EvictionPolicy, S: CacheStorage> RemovableCache for GenericCache<P, S> {
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        GenericCache::remove(self, key)
+    }
+}
+
+impl<P: EvictionAsStoragePolicy> SizedCache for EvictionCache<P> {
+    fn len(&self) -> usize {
+        EvictionCache::len(self)
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        EvictionCache::contains(self, key)
+    }
+}
+
+impl<P: EvictionAsStoragePolicy> RemovableCache for EvictionCache<P> {
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        EvictionCache::remove(self, key)
+    }
+}
+
+#[cfg(feature = "validate")]
+impl<P, S> crate::DebugValidate for GenericCache<P, S>
+where
+    P: EvictionPolicy + crate::DebugValidate,
+    S: CacheStorage,
+{
+    fn debug_validate(&self) {
+        self.policy.debug_validate();
+
+        assert_eq!(
+            self.policy.len(),
+            self.storage.len(),
+            "policy out of sync with storage"
+        );
+        assert!(self.storage.len() <= self.capacity, "over capacity");
+    }
+}
+
+#[cfg(feature = "validate")]
+impl<P> crate::DebugValidate for EvictionCache<P>
+where
+    P: EvictionAsStoragePolicy + crate::DebugValidate,
+{
+    fn debug_validate(&self) {
+        self.policy.debug_validate();
+
+        assert!(self.policy.len() <= self.capacity, "over capacity");
+    }
+}
+
+// the storage is a plain map, the policy holds the structure worth looking at
+#[cfg(feature = "dump")]
+impl<P, S> crate::Dump for GenericCache<P, S>
+where
+    P: EvictionPolicy + crate::Dump,
+    S: CacheStorage,
+{
+    fn dump(&self) -> crate::StructureDump {
+        self.policy.dump()
+    }
+}
+
+#[cfg(feature = "dump")]
+impl<P> crate::Dump for EvictionCache<P>
+where
+    P: EvictionAsStoragePolicy + crate::Dump,
+{
+    fn dump(&self) -> crate::StructureDump {
+        self.policy.dump()
+    }
 }
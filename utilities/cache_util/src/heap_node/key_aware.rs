@@ -1,66 +1,14 @@
-use super::HeapNodeTrait;
-use std::cmp::Ordering;
+use super::{HeapNode, HeapNodeTrait};
 
+// Holds the key for an inner node without one, ordered by the inner node alone
+#[derive(HeapNode)]
+#[heap_node(order_by(node))]
 pub struct KeyAwareHeapNode<H>
 where
     H: HeapNodeTrait<Key = ()>,
 {
+    #[heap_node(key)]
     key: i32,
+    #[heap_node(inner)]
     node: H,
 }
-
-impl<H> PartialEq for KeyAwareHeapNode<H>
-where
-    H: HeapNodeTrait<Key = ()>,
-{
-    fn eq(&self, other: &Self) -> bool {
-        self.key == other.key && self.node == other.node
-    }
-}
-impl<H> Eq for KeyAwareHeapNode<H> where H: HeapNodeTrait<Key = ()> {}
-
-impl<H> PartialOrd for KeyAwareHeapNode<H>
-where
-    H: HeapNodeTrait<Key = ()>,
-{
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-impl<H> Ord for KeyAwareHeapNode<H>
-where
-    H: HeapNodeTrait<Key = ()>,
-{
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.node.cmp(&other.node) // ignore key
-    }
-}
-
-impl<H> HeapNodeTrait for KeyAwareHeapNode<H>
-where
-    H: HeapNodeTrait<Key = ()>,
-{
-    type Key = i32;
-    type Value = H::Value; // pass value to inner node
-
-    fn new(key: Self::Key, value: Self::Value) -> Self {
-        Self {
-            key,
-            node: HeapNodeTrait::new((), value),
-        }
-    }
-
-    fn key(&self) -> &Self::Key {
-        &self.key
-    }
-    fn value(&self) -> &Self::Value {
-        self.node.value()
-    }
-    fn set_value(&mut self, value: Self::Value) {
-        self.node.set_value(value);
-    }
-
-    fn on_access(&mut self) {
-        self.node.on_access();
-    }
-}
@@ -1,10 +1,24 @@
 use super::HeapNodeTrait;
 
 use std::cmp::Ordering;
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+// Logical clock shared by all caches. SystemTime can repeat between two quick accesses
+// on coarse clocks (or step backwards), which broke ties between keys at random.
+static CLOCK: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn tick() -> u64 {
+    CLOCK.fetch_add(1, AtomicOrdering::Relaxed)
+}
+
+// The tick the next access will get, to tell how long ago a last_access() was
+pub fn logical_time() -> u64 {
+    CLOCK.load(AtomicOrdering::Relaxed)
+}
 
 pub struct LRUHeapNode {
-    last_access: SystemTime,
+    last_access: u64,
+    inserted_at: u64,
 }
 
 impl Default for LRUHeapNode {
@@ -36,8 +50,11 @@ impl HeapNodeTrait for LRUHeapNode {
     type Value = ();
 
     fn new(_key: Self::Key, _value: Self::Value) -> Self {
+        let now = tick();
+
         Self {
-            last_access: SystemTime::now(),
+            last_access: now,
+            inserted_at: now,
         }
     }
 
@@ -50,6 +67,13 @@ impl HeapNodeTrait for LRUHeapNode {
     fn set_value(&mut self, _value: Self::Value) {}
 
     fn on_access(&mut self) {
-        self.last_access = SystemTime::now();
+        self.last_access = tick();
+    }
+
+    fn last_access(&self) -> Option<u64> {
+        Some(self.last_access)
+    }
+    fn inserted_at(&self) -> Option<u64> {
+        Some(self.inserted_at)
     }
 }
@@ -1,6 +1,9 @@
 mod traits;
 pub use traits::*;
 
+// writes the delegation of the wrappers below, see heap_node_derive
+pub use heap_node_derive::HeapNode;
+
 mod lru;
 pub use lru::*;
 
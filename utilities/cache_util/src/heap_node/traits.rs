@@ -9,4 +9,15 @@ pub trait HeapNodeTrait: Ord {
     fn set_value(&mut self, value: Self::Value);
 
     fn on_access(&mut self);
+
+    // Read-only policy metadata, None where the node doesn't track it
+    fn access_count(&self) -> Option<u64> {
+        None
+    }
+    fn last_access(&self) -> Option<u64> {
+        None
+    }
+    fn inserted_at(&self) -> Option<u64> {
+        None
+    }
 }
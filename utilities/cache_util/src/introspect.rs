@@ -0,0 +1,51 @@
+use crate::HeapNodeTrait;
+
+// What a policy knows about one cached key, times being logical_time() ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyMetadata {
+    pub access_count: Option<u64>, // LFU only, the insertion counting as the first access
+    pub last_access: Option<u64>,
+    pub inserted_at: Option<u64>,
+    pub eviction_rank: usize, // 0 for the key evict() would return next
+}
+
+impl KeyMetadata {
+    pub(crate) fn from_node<H: HeapNodeTrait>(node: &H, eviction_rank: usize) -> Self {
+        Self {
+            access_count: node.access_count(),
+            last_access: node.last_access(),
+            inserted_at: node.inserted_at(),
+            eviction_rank,
+        }
+    }
+}
+
+// Read-only view of a policy, to find out why a key was (or is about to be) evicted
+pub trait Introspect {
+    // None if `key` isn't cached
+    fn inspect(&self, key: &i32) -> Option<KeyMetadata>;
+
+    // The next `n` keys evict() would return, in that order, without evicting them
+    fn victims(&self, n: usize) -> Vec<i32>;
+}
+
+// For the heap policies, whose eviction order is their node order: O(n)
+pub(crate) fn rank_among<'a, T: Ord + 'a>(node: &T, nodes: impl Iterator<Item = &'a T>) -> usize {
+    nodes.filter(|&other| other < node).count()
+}
+
+// The keys of the `n` smallest nodes, smallest first: O(len + n log n)
+pub(crate) fn smallest_keys<'a, T: Ord + 'a>(
+    entries: impl Iterator<Item = (i32, &'a T)>,
+    n: usize,
+) -> Vec<i32> {
+    let mut entries = entries.collect::<Vec<_>>();
+
+    if n < entries.len() {
+        entries.select_nth_unstable_by(n, |a, b| a.1.cmp(b.1));
+        entries.truncate(n);
+    }
+    entries.sort_unstable_by(|a, b| a.1.cmp(b.1));
+
+    entries.into_iter().map(|(key, _)| key).collect()
+}
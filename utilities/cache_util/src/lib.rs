@@ -1,4 +1,5 @@
-#![feature(map_many_mut)]
+// so that #[derive(HeapNode)], which expands to ::cache_util paths, also works in here
+extern crate self as cache_util;
 
 mod cache;
 pub use cache::*;
@@ -17,3 +18,28 @@ pub use heap_node::*;
 
 mod mock;
 pub use mock::*;
+
+mod trace;
+pub use trace::*;
+
+mod registry;
+pub use registry::*;
+
+mod introspect;
+pub use introspect::*;
+
+mod builder;
+pub use builder::*;
+
+mod metrics;
+pub use metrics::*;
+
+#[cfg(feature = "validate")]
+mod validate;
+#[cfg(feature = "validate")]
+pub use validate::*;
+
+#[cfg(feature = "dump")]
+mod dump;
+#[cfg(feature = "dump")]
+pub use dump::*;
@@ -0,0 +1,129 @@
+use super::CacheStats;
+
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+// Content-Type of what render() returns
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+type Metric = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn(&CacheStats) -> u64,
+);
+
+// (name, type, help, value)
+const METRICS: &[Metric] = &[
+    (
+        "cache_hits_total",
+        "counter",
+        "Gets that found the key.",
+        CacheStats::hits,
+    ),
+    (
+        "cache_misses_total",
+        "counter",
+        "Gets that missed, expired entries included.",
+        CacheStats::misses,
+    ),
+    (
+        "cache_puts_total",
+        "counter",
+        "Puts, updates of cached keys included.",
+        CacheStats::puts,
+    ),
+    (
+        "cache_evictions_total",
+        "counter",
+        "Keys evicted to make room for another.",
+        CacheStats::evictions,
+    ),
+    (
+        "cache_load_failures_total",
+        "counter",
+        "Misses whose value failed to load.",
+        CacheStats::load_failures,
+    ),
+    (
+        "cache_size",
+        "gauge",
+        "Keys held, as of the last put.",
+        CacheStats::size,
+    ),
+    (
+        "cache_capacity",
+        "gauge",
+        "Keys held at most.",
+        CacheStats::capacity,
+    ),
+];
+
+static GLOBAL: Lazy<Arc<MetricsRegistry>> = Lazy::new(Arc::default);
+
+// The registry every in-process cache can register in, for one endpoint to scrape them all
+pub fn global_metrics() -> Arc<MetricsRegistry> {
+    GLOBAL.clone()
+}
+
+// Named CacheStats, rendered in the Prometheus text exposition format with a `cache` label
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    caches: Mutex<BTreeMap<String, CacheStats>>,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // replaces whatever was registered under `name`
+    pub fn register(&self, name: &str, stats: CacheStats) {
+        self.caches.lock().unwrap().insert(name.to_string(), stats);
+    }
+
+    pub fn unregister(&self, name: &str) -> Option<CacheStats> {
+        self.caches.lock().unwrap().remove(name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.caches.lock().unwrap().keys().cloned().collect()
+    }
+
+    // one HELP/TYPE block per metric, one sample per cache, by name:
+    //
+    //     # HELP cache_hits_total Gets that found the key.
+    //     # TYPE cache_hits_total counter
+    //     cache_hits_total{cache="sessions"} 42
+    pub fn render(&self) -> String {
+        let caches = self.caches.lock().unwrap();
+        let mut text = String::new();
+
+        for &(metric, kind, help, value) in METRICS {
+            writeln!(text, "# HELP {} {}", metric, help).unwrap();
+            writeln!(text, "# TYPE {} {}", metric, kind).unwrap();
+
+            for (name, stats) in caches.iter() {
+                writeln!(
+                    text,
+                    "{}{{cache=\"{}\"}} {}",
+                    metric,
+                    escape_label(name),
+                    value(stats)
+                )
+                .unwrap();
+            }
+        }
+
+        text
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,90 @@
+use super::CacheStats;
+use crate::{Cache, RemovableCache, SizedCache};
+
+// Counts into `stats` what goes through to `cache`, e.g. a registered implementation:
+//
+//     let stats = CacheStats::default();
+//     let cache = InstrumentedCache::new((implementation.new)(1024), 1024, stats.clone());
+//     global_metrics().register("sessions", stats);
+pub struct InstrumentedCache<C: SizedCache + ?Sized> {
+    cache: Box<C>,
+    stats: CacheStats,
+}
+
+impl<C: SizedCache + ?Sized> InstrumentedCache<C> {
+    pub fn new(cache: Box<C>, capacity: usize, stats: CacheStats) -> Self {
+        stats.set_capacity(capacity);
+        stats.set_size(cache.len());
+
+        Self { cache, stats }
+    }
+
+    pub fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+
+    pub fn into_inner(self) -> Box<C> {
+        self.cache
+    }
+
+    // get(), or on a miss the value `load` finds for the key, put() before returning it;
+    // a failed load is counted and returned as is
+    pub fn get_or_load<E>(
+        &mut self,
+        key: i32,
+        load: impl FnOnce(i32) -> Result<i32, E>,
+    ) -> Result<i32, E> {
+        if let Some(value) = self.get(&key) {
+            return Ok(value);
+        }
+
+        match load(key) {
+            Ok(value) => {
+                self.put(key, value);
+                Ok(value)
+            }
+            Err(err) => {
+                self.stats.record_load_failure();
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<C: SizedCache + ?Sized> Cache for InstrumentedCache<C> {
+    fn put(&mut self, key: i32, value: i32) {
+        let inserted = !self.cache.contains(&key);
+        let before = self.cache.len();
+
+        self.cache.put(key, value);
+
+        // a new key that didn't grow the cache took an evicted key's place
+        let after = self.cache.len();
+        let evicted = inserted && before > 0 && after <= before;
+        self.stats.record_put(evicted, after);
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        let value = self.cache.get(key);
+        self.stats.record_get(value.is_some());
+        value
+    }
+}
+
+impl<C: SizedCache + ?Sized> SizedCache for InstrumentedCache<C> {
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.cache.contains(key)
+    }
+}
+
+impl<C: RemovableCache + ?Sized> RemovableCache for InstrumentedCache<C> {
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        let value = self.cache.remove(key);
+        self.stats.set_size(self.cache.len());
+        value
+    }
+}
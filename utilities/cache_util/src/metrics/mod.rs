@@ -0,0 +1,13 @@
+mod stats;
+pub use stats::*;
+
+mod instrumented;
+pub use instrumented::*;
+
+mod exporter;
+pub use exporter::*;
+
+#[cfg(feature = "http")]
+mod server;
+#[cfg(feature = "http")]
+pub use server::*;
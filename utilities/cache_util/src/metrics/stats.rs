@@ -0,0 +1,123 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    puts: AtomicU64,
+    evictions: AtomicU64,
+    load_failures: AtomicU64,
+    size: AtomicU64,     // gauge, as of the last put
+    capacity: AtomicU64, // gauge
+}
+
+// Shared cache counters, every clone reading and bumping the same ones
+#[derive(Debug, Clone, Default)]
+pub struct CacheStats {
+    counters: Arc<Counters>,
+}
+
+impl CacheStats {
+    pub fn hits(&self) -> u64 {
+        self.counters.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.counters.misses.load(Ordering::Relaxed)
+    }
+
+    pub fn gets(&self) -> u64 {
+        self.hits() + self.misses()
+    }
+
+    pub fn puts(&self) -> u64 {
+        self.counters.puts.load(Ordering::Relaxed)
+    }
+
+    pub fn evictions(&self) -> u64 {
+        self.counters.evictions.load(Ordering::Relaxed)
+    }
+
+    pub fn load_failures(&self) -> u64 {
+        self.counters.load_failures.load(Ordering::Relaxed)
+    }
+
+    pub fn size(&self) -> u64 {
+        self.counters.size.load(Ordering::Relaxed)
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.counters.capacity.load(Ordering::Relaxed)
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        let gets = self.gets();
+        if gets == 0 {
+            return 0.0;
+        }
+
+        self.hits() as f64 / gets as f64
+    }
+
+    // a get() that found the key or not
+    pub fn record_get(&self, hit: bool) {
+        let counter = match hit {
+            true => &self.counters.hits,
+            false => &self.counters.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // a put() that evicted a key or not, leaving `size` keys
+    pub fn record_put(&self, evicted: bool, size: usize) {
+        self.counters.puts.fetch_add(1, Ordering::Relaxed);
+        if evicted {
+            self.counters.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        self.set_size(size);
+    }
+
+    // a value that couldn't be loaded after a miss, see InstrumentedCache::get_or_load()
+    pub fn record_load_failure(&self) {
+        self.counters.load_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn set_size(&self, size: usize) {
+        self.counters.size.store(size as u64, Ordering::Relaxed);
+    }
+
+    pub fn set_capacity(&self, capacity: usize) {
+        self.counters
+            .capacity
+            .store(capacity as u64, Ordering::Relaxed);
+    }
+
+    // the counters, not the gauges
+    pub fn reset(&self) {
+        self.counters.hits.store(0, Ordering::Relaxed);
+        self.counters.misses.store(0, Ordering::Relaxed);
+        self.counters.puts.store(0, Ordering::Relaxed);
+        self.counters.evictions.store(0, Ordering::Relaxed);
+        self.counters.load_failures.store(0, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "gets: {}, hits: {}, misses: {}, puts: {}, evictions: {}, load failures: {}, size: {}/{}, hit ratio: {:.4}",
+            self.gets(),
+            self.hits(),
+            self.misses(),
+            self.puts(),
+            self.evictions(),
+            self.load_failures(),
+            self.size(),
+            self.capacity(),
+            self.hit_ratio()
+        )
+    }
+}
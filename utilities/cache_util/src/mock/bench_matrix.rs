@@ -0,0 +1,110 @@
+use super::{CacheOperation, SEED, WORKLOAD_ENV, Workload};
+use crate::trace_from_env;
+use once_cell::sync::Lazy;
+use std::fmt;
+use std::str::FromStr;
+
+// Comma-separated overrides for the axes of the benchmark matrix, e.g.
+// CACHE_BENCH_CAPACITIES="100,1000" CACHE_BENCH_WORKLOADS="uniform,scan@0.1"
+pub const BENCH_CAPACITIES_ENV: &str = "CACHE_BENCH_CAPACITIES";
+pub const BENCH_OPERATIONS_ENV: &str = "CACHE_BENCH_OPERATIONS";
+pub const BENCH_WORKLOADS_ENV: &str = "CACHE_BENCH_WORKLOADS";
+
+const DEFAULT_CAPACITIES: &[usize] = &[100, 10_000];
+const DEFAULT_OPERATIONS: &[usize] = &[10_000, 100_000];
+const DEFAULT_WORKLOADS: &[&str] = &["uniform", "zipfian:0.99"];
+
+// operations of the given length for one workload
+type Generator = Box<dyn Fn(usize) -> Vec<CacheOperation>>;
+
+// One point of the matrix, with its operations generated up front
+pub struct BenchPoint {
+    pub workload: String, // a Workload spec, or "trace" for CACHE_TRACE
+    pub capacity: usize,
+    pub operations: Vec<CacheOperation>,
+}
+
+// "workload=zipfian:0.99,capacity=100,operations=10000", the BenchmarkId parameter
+// that bench_util splits back into tags
+impl fmt::Display for BenchPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "workload={},capacity={},operations={}",
+            self.workload,
+            self.capacity,
+            self.operations.len()
+        )
+    }
+}
+
+fn axis_from_env<T: FromStr>(name: &str) -> Option<Vec<T>> {
+    let list = std::env::var(name).ok()?;
+
+    let values = list
+        .split(',')
+        .map(|value| value.trim().parse())
+        .collect::<Result<Vec<T>, _>>();
+
+    match values {
+        Ok(values) if !values.is_empty() => Some(values),
+        _ => panic!("invalid {}=\"{}\"", name, list),
+    }
+}
+
+// (name, operations generator) for each workload: CACHE_BENCH_WORKLOADS, else the single
+// CACHE_TRACE or CACHE_WORKLOAD the other benches and tests use, else the defaults
+fn workloads() -> Vec<(String, Generator)> {
+    let from_spec = |spec: String| {
+        let workload = match spec.parse::<Workload>() {
+            Ok(workload) => workload,
+            Err(err) => panic!("invalid workload \"{}\": {}", spec, err),
+        };
+        let generate: Generator = Box::new(move |len| workload.operations(SEED, len));
+
+        (spec, generate)
+    };
+
+    if let Some(specs) = axis_from_env::<String>(BENCH_WORKLOADS_ENV) {
+        return specs.into_iter().map(from_spec).collect();
+    }
+
+    // a trace is cut to each operation count, at most its own length
+    if let Some(trace) = trace_from_env() {
+        let generate: Generator = Box::new(move |len| trace[..len.min(trace.len())].to_vec());
+
+        return vec![("trace".to_string(), generate)];
+    }
+
+    if let Ok(spec) = std::env::var(WORKLOAD_ENV) {
+        return vec![from_spec(spec)];
+    }
+
+    DEFAULT_WORKLOADS
+        .iter()
+        .map(|spec| from_spec(spec.to_string()))
+        .collect()
+}
+
+// Every workload x operation count x capacity, in that nesting order
+pub static BENCH_MATRIX: Lazy<Vec<BenchPoint>> = Lazy::new(|| {
+    let capacities = axis_from_env(BENCH_CAPACITIES_ENV).unwrap_or(DEFAULT_CAPACITIES.to_vec());
+    let lens = axis_from_env(BENCH_OPERATIONS_ENV).unwrap_or(DEFAULT_OPERATIONS.to_vec());
+
+    let mut points = Vec::new();
+    for (workload, generate) in workloads() {
+        for &len in &lens {
+            let operations = generate(len);
+
+            for &capacity in &capacities {
+                points.push(BenchPoint {
+                    workload: workload.clone(),
+                    capacity,
+                    operations: operations.clone(),
+                });
+            }
+        }
+    }
+
+    points
+});
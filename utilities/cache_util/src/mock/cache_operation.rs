@@ -1,5 +1,6 @@
 use super::variables_range::*;
-use super::{DefaultOperationsRangeProvider, OperationsRangeProvider};
+use super::{DefaultOperationsRangeProvider, OperationsRangeProvider, Workload};
+use crate::trace_from_env;
 use once_cell::sync::Lazy;
 use proptest::prelude::*;
 use rand::distributions::{Distribution, Standard};
@@ -7,7 +8,7 @@ use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 
 // Define an enum to represent cache operations
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CacheOperation {
     Put { key: i32, value: i32 },
     Get { key: i32 },
@@ -44,11 +45,22 @@ impl Arbitrary for CacheOperation {
     }
 }
 
+// Uniform 50/50 put/get by default, or the workload picked by CACHE_WORKLOAD,
+// or the trace file at CACHE_TRACE which takes precedence
 pub static OPERATIONS: Lazy<Vec<CacheOperation>> = Lazy::new(|| {
+    if let Some(operations) = trace_from_env() {
+        return operations;
+    }
+
+    let operations_range = DefaultOperationsRangeProvider.operations_range();
+
+    if let Some(workload) = Workload::from_env() {
+        return workload.operations(SEED, operations_range.count());
+    }
+
     let mut rng = StdRng::seed_from_u64(SEED);
 
-    DefaultOperationsRangeProvider
-        .operations_range()
+    operations_range
         .map(|_| rng.r#gen())
         .collect::<Vec<CacheOperation>>()
 });
@@ -0,0 +1,283 @@
+use super::CacheOperation;
+use super::variables_range::value_range;
+use crate::{CacheImplementation, LeetCodeCache};
+use proptest::prelude::*;
+use std::ops::RangeInclusive;
+
+// Fixtures shared by the q146/q460 tests, sized so that every key is both hit and evicted
+
+// Twice as many keys as the capacity
+pub fn evicting_key_range(capacity: usize) -> RangeInclusive<i32> {
+    0..=capacity as i32 * 2
+}
+
+// A put or a get over evicting_key_range(capacity)
+pub fn evicting_operation_strategy(capacity: usize) -> BoxedStrategy<CacheOperation> {
+    let keys = evicting_key_range(capacity);
+
+    prop_oneof![
+        keys.clone().prop_map(|key| CacheOperation::Get { key }),
+        (keys, value_range()).prop_map(|(key, value)| CacheOperation::Put { key, value }),
+    ]
+    .boxed()
+}
+
+// A capacity of 1 to 16 and up to eight operations per slot over twice as many keys
+pub fn evicting_operations_strategy() -> impl Strategy<Value = (usize, Vec<CacheOperation>)> {
+    (1..=16usize).prop_flat_map(|capacity| {
+        (
+            Just(capacity),
+            prop::collection::vec(evicting_operation_strategy(capacity), 0..=capacity * 8),
+        )
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemovalStep {
+    Get(i32),
+    Put(i32, i32),
+    Remove(i32),
+}
+
+// evicting_operations_strategy() with removals, some of keys never put
+pub fn removal_steps_strategy() -> impl Strategy<Value = (usize, Vec<RemovalStep>)> {
+    (1..=16usize).prop_flat_map(|capacity| {
+        let keys = evicting_key_range(capacity);
+        let step = prop_oneof![
+            keys.clone().prop_map(RemovalStep::Get),
+            (keys.clone(), value_range()).prop_map(|(key, value)| RemovalStep::Put(key, value)),
+            keys.prop_map(RemovalStep::Remove),
+        ];
+
+        (
+            Just(capacity),
+            prop::collection::vec(step, 0..=capacity * 8),
+        )
+    })
+}
+
+// up to twice the capacity in items over twice as many keys, so both the batch and the
+// evicting remainder of extend() run, with repeated keys in each
+pub fn bulk_load_strategy() -> impl Strategy<Value = (usize, Vec<(i32, i32)>, Vec<CacheOperation>)>
+{
+    (1..=64usize).prop_flat_map(|capacity| {
+        let item = (evicting_key_range(capacity), value_range());
+
+        (
+            Just(capacity),
+            prop::collection::vec(item, 0..=capacity * 2),
+            prop::collection::vec(evicting_operation_strategy(capacity), 0..=capacity * 4),
+        )
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Batch {
+    Gets(Vec<i32>),
+    Puts(Vec<(i32, i32)>),
+}
+
+// batches from empty to twice the capacity over twice as many keys, so both the rebuild and
+// the sift-each path of the heap run, and puts both fit and evict
+pub fn batches_strategy() -> impl Strategy<Value = (usize, Vec<Batch>)> {
+    (1..=32usize).prop_flat_map(|capacity| {
+        let keys = evicting_key_range(capacity);
+        let batch = prop_oneof![
+            prop::collection::vec(keys.clone(), 0..=capacity * 2).prop_map(Batch::Gets),
+            prop::collection::vec((keys, value_range()), 0..=capacity * 2).prop_map(Batch::Puts),
+        ];
+
+        (Just(capacity), prop::collection::vec(batch, 0..=16))
+    })
+}
+
+pub type FromIter = fn(i32, Vec<(i32, i32)>) -> Box<dyn LeetCodeCache>;
+
+// The caches with a bulk path, by registered name, for check_bulk_load():
+//
+//     const BULK_LOADED: &[(&str, FromIter)] = cache_util::bulk_loaded![
+//         "vec_hashmap" => vec_hashmap::LRUCache,
+//         "d_ary_heap_4_eviction" => d_ary_heap::LRUEvictionCache<4>,
+//     ];
+#[macro_export]
+macro_rules! bulk_loaded {
+    ($($name:literal => $ty:ty),* $(,)?) => {
+        &[$(($name, |capacity, items| Box::new(<$ty>::from_iter(capacity, items)))),*]
+    };
+}
+
+// from_iter() must leave the same cache as put() one by one: same gets, same evictions after
+pub fn check_bulk_load<'a>(
+    implementations: impl IntoIterator<Item = &'a CacheImplementation>,
+    bulk_loaded: &[(&str, FromIter)],
+    capacity: usize,
+    items: &[(i32, i32)],
+    operations: &[CacheOperation],
+) {
+    let implementations = implementations.into_iter().collect::<Vec<_>>();
+
+    for &(name, from_iter) in bulk_loaded {
+        let implementation = implementations
+            .iter()
+            .find(|implementation| implementation.name == name)
+            .unwrap_or_else(|| panic!("{} is not registered", name));
+
+        let mut expected = (implementation.new)(capacity as i32);
+        for &(key, value) in items {
+            expected.put(key, value);
+        }
+        let mut cache = from_iter(capacity as i32, items.to_vec());
+
+        cache.debug_validate();
+        assert_eq!(cache.len(), expected.len(), "{} len after from_iter", name);
+
+        for operation in operations {
+            match *operation {
+                CacheOperation::Put { key, value } => {
+                    expected.put(key, value);
+                    cache.put(key, value);
+                }
+                CacheOperation::Get { key } => {
+                    assert_eq!(
+                        cache.get(key),
+                        expected.get(key),
+                        "{} differs on get({})",
+                        name,
+                        key
+                    );
+                }
+            }
+            cache.debug_validate();
+        }
+    }
+}
+
+// get_many()/put_many() of `$ty` must match get()/put() one by one on the same type
+#[macro_export]
+macro_rules! test_batches_with {
+    ($ty:ty, $capacity:expr, $batches:expr) => {{
+        let mut batched = <$ty>::new($capacity as i32);
+        let mut expected = <$ty>::new($capacity as i32);
+
+        for batch in $batches {
+            match batch {
+                $crate::Batch::Gets(keys) => {
+                    let values = keys
+                        .iter()
+                        .map(|&key| expected.get(key))
+                        .collect::<Vec<_>>();
+                    assert_eq!(
+                        batched.get_many(keys),
+                        values,
+                        "{} get_many({:?})",
+                        stringify!($ty),
+                        keys
+                    );
+                }
+                $crate::Batch::Puts(items) => {
+                    for &(key, value) in items {
+                        expected.put(key, value);
+                    }
+                    batched.put_many(items.iter().copied());
+                }
+            }
+
+            $crate::LeetCodeCache::debug_validate(&batched);
+            assert_eq!(batched.len(), expected.len(), "{} len", stringify!($ty));
+        }
+    }};
+}
+
+// inspect() and victims() of `$ty` must agree with each other, with the keys the next puts
+// evict, and with the access counts if `$counts_accesses` (None otherwise)
+#[macro_export]
+macro_rules! test_introspection_with {
+    ($ty:ty, $capacity:expr, $operations:expr, $counts_accesses:expr) => {{
+        let mut cache = <$ty>::new($capacity as i32);
+        let mut access_counts = ::std::collections::HashMap::new();
+
+        for operation in $operations {
+            match *operation {
+                $crate::CacheOperation::Put { key, value } => {
+                    *access_counts.entry(key).or_insert(0) += 1;
+                    cache.put(key, value);
+                    access_counts.retain(|&key, _| cache.contains(key));
+                }
+                $crate::CacheOperation::Get { key } => {
+                    if cache.get(key) != -1 {
+                        *access_counts.get_mut(&key).unwrap() += 1;
+                    }
+                }
+            }
+
+            let victims = cache.victims(cache.len());
+            assert_eq!(victims.len(), cache.len(), "{} victims", stringify!($ty));
+
+            for (rank, &key) in victims.iter().enumerate() {
+                let metadata = cache.inspect(key).expect("victims are cached");
+                assert_eq!(
+                    metadata.eviction_rank,
+                    rank,
+                    "{} rank of {}",
+                    stringify!($ty),
+                    key
+                );
+                let access_count = access_counts.get(&key).copied();
+                assert_eq!(
+                    metadata.access_count,
+                    access_count.filter(|_| $counts_accesses),
+                    "{} access count of {}",
+                    stringify!($ty),
+                    key
+                );
+                assert!(metadata.inserted_at <= metadata.last_access);
+                assert!(metadata.last_access < Some($crate::logical_time()));
+            }
+        }
+
+        if cache.len() == $capacity {
+            for fresh in 0..3 {
+                let victim = cache.victims(1)[0];
+                cache.put(i32::MAX - fresh, 0); // never in evicting_key_range()
+                assert!(
+                    !cache.contains(victim),
+                    "{} kept {}",
+                    stringify!($ty),
+                    victim
+                );
+            }
+        }
+        assert_eq!(cache.inspect(-1), None);
+    }};
+}
+
+// every cached key is dumped once, the index points at its node, and edges stay between
+// dumped nodes
+#[cfg(feature = "dump")]
+pub fn check_dump(name: &str, cache: &dyn LeetCodeCache, dump: &crate::StructureDump) {
+    let ids = dump
+        .nodes
+        .iter()
+        .map(|node| (node.id, node.key))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    assert_eq!(ids.len(), dump.nodes.len(), "{} dumps a node twice", name);
+    assert_eq!(dump.nodes.len(), cache.len(), "{} nodes", name);
+    assert_eq!(dump.index.len(), cache.len(), "{} index", name);
+
+    for (&key, id) in &dump.index {
+        assert!(cache.contains(key), "{} indexes {}", name, key);
+        assert_eq!(ids.get(id), Some(&key), "{} index of {}", name, key);
+    }
+    for edge in &dump.edges {
+        assert!(
+            ids.contains_key(&edge.from) && ids.contains_key(&edge.to),
+            "{} {:?}",
+            name,
+            edge
+        );
+    }
+
+    assert!(dump.to_dot().starts_with("digraph"), "{} DOT", name);
+    assert!(dump.to_json().starts_with('{'), "{} JSON", name);
+}
@@ -0,0 +1,165 @@
+use super::{BENCH_MATRIX, BenchPoint, CacheOperation};
+use crate::{CacheImplementation, LeetCodeCache};
+use hdrhistogram::Histogram;
+use serde::Serialize;
+use std::fmt;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::time::Instant;
+
+// 3 significant digits, the resolution HdrHistogram recommends for latencies
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+// Latency of each get, put and both, in nanoseconds; every sample includes one Instant::now()
+pub struct LatencyHistograms {
+    pub get: Histogram<u64>,
+    pub put: Histogram<u64>,
+    pub all: Histogram<u64>,
+}
+
+impl LatencyHistograms {
+    pub fn new() -> Self {
+        let histogram =
+            || Histogram::new(SIGNIFICANT_DIGITS).expect("3 significant digits are always valid");
+
+        Self {
+            get: histogram(),
+            put: histogram(),
+            all: histogram(),
+        }
+    }
+
+    // Replays `operations` on `cache`, timing each of them separately
+    pub fn record(cache: &mut dyn LeetCodeCache, operations: &[CacheOperation]) -> Self {
+        let mut histograms = Self::new();
+
+        for operation in operations {
+            let (histogram, elapsed) = match *operation {
+                CacheOperation::Put { key, value } => {
+                    let start = Instant::now();
+                    cache.put(black_box(key), black_box(value));
+                    (&mut histograms.put, start.elapsed())
+                }
+                CacheOperation::Get { key } => {
+                    let start = Instant::now();
+                    black_box(cache.get(black_box(key)));
+                    (&mut histograms.get, start.elapsed())
+                }
+            };
+
+            let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+            histogram.saturating_record(nanos);
+            histograms.all.saturating_record(nanos);
+        }
+
+        histograms
+    }
+}
+
+impl Default for LatencyHistograms {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The percentiles uploaded by bench_util, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub mean: f64,
+    pub p50: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+impl From<&Histogram<u64>> for LatencySummary {
+    fn from(histogram: &Histogram<u64>) -> Self {
+        Self {
+            count: histogram.len(),
+            mean: histogram.mean(),
+            p50: histogram.value_at_quantile(0.5),
+            p99: histogram.value_at_quantile(0.99),
+            p999: histogram.value_at_quantile(0.999),
+            max: histogram.max(),
+        }
+    }
+}
+
+impl fmt::Display for LatencySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "p50: {}ns, p99: {}ns, p999: {}ns, max: {}ns",
+            self.p50, self.p99, self.p999, self.max
+        )
+    }
+}
+
+// One implementation on one point of BENCH_MATRIX
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencyReport {
+    pub implementation: String,
+    pub workload: String,
+    pub capacity: usize,
+    pub operations: usize,
+    pub get: LatencySummary,
+    pub put: LatencySummary,
+    pub all: LatencySummary,
+}
+
+impl LatencyReport {
+    // A fresh cache of `point.capacity`, timed over `point.operations`
+    pub fn measure(implementation: &CacheImplementation, point: &BenchPoint) -> Self {
+        let mut cache = (implementation.new)(point.capacity as i32);
+        let histograms = LatencyHistograms::record(&mut *cache, &point.operations);
+
+        Self {
+            implementation: implementation.name.to_string(),
+            workload: point.workload.clone(),
+            capacity: point.capacity,
+            operations: point.operations.len(),
+            get: LatencySummary::from(&histograms.get),
+            put: LatencySummary::from(&histograms.put),
+            all: LatencySummary::from(&histograms.all),
+        }
+    }
+}
+
+impl fmt::Display for LatencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}/workload={},capacity={},operations={}",
+            self.implementation, self.workload, self.capacity, self.operations
+        )?;
+        writeln!(f, "    get: {}", self.get)?;
+        writeln!(f, "    put: {}", self.put)?;
+        write!(f, "    all: {}", self.all)
+    }
+}
+
+// Every implementation on every point of BENCH_MATRIX
+pub fn latency_reports<'a>(
+    implementations: impl Iterator<Item = &'a CacheImplementation>,
+) -> Vec<LatencyReport> {
+    implementations
+        .flat_map(|implementation| {
+            BENCH_MATRIX
+                .iter()
+                .map(move |point| LatencyReport::measure(implementation, point))
+        })
+        .collect()
+}
+
+// The JSON array bench_util uploads, creating the parent directory if needed
+pub fn write_latency_reports(path: &Path, reports: &[LatencyReport]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(writer, reports).map_err(io::Error::other)
+}
@@ -1,27 +1,36 @@
 #[macro_export]
+// Replays `$operations` (a &[CacheOperation]) on a fresh `$new` cache in each iteration
+macro_rules! bench_replay {
+    ($bencher:expr, $new:expr, $operations:expr) => {
+        $bencher.iter(|| {
+            let mut cache = $new;
+            for op in $operations.iter() {
+                match op {
+                    cache_util::CacheOperation::Put { key, value } => {
+                        cache.put(::criterion::black_box(*key), ::criterion::black_box(*value));
+                    }
+                    cache_util::CacheOperation::Get { key } => {
+                        ::criterion::black_box(cache.get(::criterion::black_box(*key)));
+                    }
+                }
+            }
+        })
+    };
+}
+
+#[macro_export]
+// One criterion benchmark named `$bench_name`, a pass over OPERATIONS at CAPACITY, which CI
+// tracks per implementation; bench_implementations! runs the whole BENCH_MATRIX instead
 macro_rules! bench_cache {
     ($bench_name:ident, $cache_type:ty) => {
         fn $bench_name(c: &mut ::criterion::Criterion) {
             let capacity = *cache_util::CAPACITY;
             c.bench_function(stringify!($bench_name), |b| {
-                b.iter(|| {
-                    let mut cache: $cache_type =
-                        <$cache_type>::new(::criterion::black_box(capacity as i32));
-                    for op in cache_util::OPERATIONS.iter() {
-                        match op {
-                            cache_util::CacheOperation::Put { key, value } => {
-                                cache.put(
-                                    ::criterion::black_box(*key),
-                                    ::criterion::black_box(*value),
-                                );
-                            }
-                            cache_util::CacheOperation::Get { key } => {
-                                ::criterion::black_box(cache.get(::criterion::black_box(*key)));
-                            }
-                            _ => unreachable!(),
-                        }
-                    }
-                })
+                cache_util::bench_replay!(
+                    b,
+                    <$cache_type>::new(::criterion::black_box(capacity as i32)),
+                    cache_util::OPERATIONS
+                )
             });
         }
     };
@@ -45,3 +54,101 @@ macro_rules! define_benchmark {
         }
     };
 }
+
+#[macro_export]
+// One criterion group over every implementation a crate registers (see register_caches!)
+// and every point of BENCH_MATRIX, called through Box<dyn LeetCodeCache> so they all pay
+// the same dispatch cost
+macro_rules! bench_implementations {
+    ($crate_name:ident) => {
+        fn bench_implementations(c: &mut ::criterion::Criterion) {
+            use cache_util::LeetCodeCache;
+
+            let mut group = c.benchmark_group(concat!(stringify!($crate_name), "_bench_all"));
+            group.sample_size(10);
+
+            for implementation in $crate_name::implementations() {
+                for point in cache_util::BENCH_MATRIX.iter() {
+                    group.throughput(::criterion::Throughput::Elements(
+                        point.operations.len() as u64
+                    ));
+                    group.bench_with_input(
+                        ::criterion::BenchmarkId::new(implementation.name, point),
+                        point,
+                        |b, point| {
+                            cache_util::bench_replay!(
+                                b,
+                                (implementation.new)(::criterion::black_box(point.capacity as i32)),
+                                point.operations
+                            )
+                        },
+                    );
+                }
+            }
+
+            group.finish();
+        }
+
+        ::criterion::criterion_group!(benches, bench_implementations);
+        ::criterion::criterion_main!(benches);
+    };
+}
+
+#[macro_export]
+// main() for a bench that times every put/get of every registered implementation over
+// BENCH_MATRIX and writes the percentiles to target/latency/<crate>_<bench>.json
+macro_rules! bench_latency {
+    ($crate_name:ident) => {
+        fn main() {
+            let reports = cache_util::latency_reports($crate_name::implementations());
+            for report in &reports {
+                println!("{}", report);
+            }
+
+            // CARGO_TARGET_TMPDIR is <target>/tmp
+            let path = ::std::path::Path::new(env!("CARGO_TARGET_TMPDIR"))
+                .with_file_name("latency")
+                .join(concat!(
+                    stringify!($crate_name),
+                    "_",
+                    env!("CARGO_CRATE_NAME"),
+                    ".json"
+                ));
+            if let Err(err) = cache_util::write_latency_reports(&path, &reports) {
+                panic!("failed to write {}: {}", path.display(), err);
+            }
+            println!("latency reports written to {}", path.display());
+        }
+    };
+}
+
+#[macro_export]
+// main() for a bench that counts the allocations of every registered implementation over
+// BENCH_MATRIX and writes them to target/memory/<crate>_<bench>.json
+macro_rules! bench_memory {
+    ($crate_name:ident) => {
+        #[global_allocator]
+        static ALLOCATOR: cache_util::CountingAllocator = cache_util::CountingAllocator;
+
+        fn main() {
+            let reports = cache_util::memory_reports($crate_name::implementations());
+            for (name, report) in &reports {
+                println!("{}: {}", name, report);
+            }
+
+            // CARGO_TARGET_TMPDIR is <target>/tmp
+            let path = ::std::path::Path::new(env!("CARGO_TARGET_TMPDIR"))
+                .with_file_name("memory")
+                .join(concat!(
+                    stringify!($crate_name),
+                    "_",
+                    env!("CARGO_CRATE_NAME"),
+                    ".json"
+                ));
+            if let Err(err) = cache_util::write_memory_reports(&path, &reports) {
+                panic!("failed to write {}: {}", path.display(), err);
+            }
+            println!("memory reports written to {}", path.display());
+        }
+    };
+}
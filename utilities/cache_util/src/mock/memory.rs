@@ -0,0 +1,230 @@
+use super::{BENCH_MATRIX, BenchPoint, CacheOperation};
+use crate::{CacheImplementation, LeetCodeCache};
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::hint::black_box;
+use std::io::{self, BufWriter};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static BYTES_FREED: AtomicUsize = AtomicUsize::new(0);
+
+// The system allocator, counting every allocation; install it in a bench binary with
+//
+//     #[global_allocator]
+//     static ALLOCATOR: cache_util::CountingAllocator = cache_util::CountingAllocator;
+//
+// a realloc counts as one allocation of the new size and a free of the old one
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    }
+
+    fn record_free(size: usize) {
+        BYTES_FREED.fetch_add(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded as is, the caller upholds alloc's contract
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: forwarded as is, the caller upholds alloc_zeroed's contract
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: forwarded as is, `ptr` was allocated by System with `layout`
+        unsafe { System.dealloc(ptr, layout) };
+        Self::record_free(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: forwarded as is, `ptr` was allocated by System with `layout`
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            Self::record_free(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Counters since the process started, only moving when CountingAllocator is the global allocator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    pub allocations: usize,
+    pub bytes_allocated: usize,
+    pub bytes_freed: usize,
+}
+
+impl AllocationStats {
+    pub fn now() -> Self {
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+            bytes_freed: BYTES_FREED.load(Ordering::Relaxed),
+        }
+    }
+
+    // What happened between `earlier` and self
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            allocations: self.allocations - earlier.allocations,
+            bytes_allocated: self.bytes_allocated - earlier.bytes_allocated,
+            bytes_freed: self.bytes_freed - earlier.bytes_freed,
+        }
+    }
+
+    // Allocated and not yet freed, negative when a span frees more than it allocates
+    pub fn live_bytes(&self) -> isize {
+        self.bytes_allocated as isize - self.bytes_freed as isize
+    }
+}
+
+// One point of BENCH_MATRIX: construction, then a replay of its operations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemorySample {
+    pub operations: usize,
+    pub entries: usize,
+    pub construction: AllocationStats,
+    pub replay: AllocationStats,
+}
+
+impl MemorySample {
+    pub fn measure(implementation: &CacheImplementation, point: &BenchPoint) -> Self {
+        let before = AllocationStats::now();
+        let mut cache = (implementation.new)(point.capacity as i32);
+        let constructed = AllocationStats::now();
+
+        replay(&mut *cache, &point.operations);
+        let replayed = AllocationStats::now();
+
+        let sample = Self {
+            operations: point.operations.len(),
+            entries: cache.len(),
+            construction: constructed.since(&before),
+            replay: replayed.since(&constructed),
+        };
+        drop(cache);
+
+        sample
+    }
+
+    // Everything the cache still holds after the replay, over its entries
+    pub fn bytes_per_entry(&self) -> f64 {
+        let live = self.construction.live_bytes() + self.replay.live_bytes();
+
+        live as f64 / self.entries.max(1) as f64
+    }
+}
+
+fn replay(cache: &mut dyn LeetCodeCache, operations: &[CacheOperation]) {
+    for operation in operations {
+        match *operation {
+            CacheOperation::Put { key, value } => cache.put(black_box(key), black_box(value)),
+            CacheOperation::Get { key } => {
+                black_box(cache.get(black_box(key)));
+            }
+        }
+    }
+}
+
+// One implementation's numbers, uploaded by bench_util as a memory_20261019 point tagged by
+// implementation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MemoryReport {
+    // over the replays of every point, construction excluded
+    pub allocations_per_op: f64,
+    pub bytes_allocated_per_op: f64,
+    // over every point, construction included
+    pub bytes_allocated: usize,
+    // after the replay leaving the most entries, i.e. the steady state of the largest cache
+    pub bytes_per_entry: f64,
+}
+
+impl MemoryReport {
+    pub fn from_samples(samples: &[MemorySample]) -> Self {
+        let operations = samples.iter().map(|s| s.operations).sum::<usize>().max(1) as f64;
+        let allocations = samples.iter().map(|s| s.replay.allocations).sum::<usize>();
+        let replay_bytes = samples
+            .iter()
+            .map(|s| s.replay.bytes_allocated)
+            .sum::<usize>();
+        let bytes_allocated = samples
+            .iter()
+            .map(|s| s.construction.bytes_allocated + s.replay.bytes_allocated)
+            .sum();
+        let bytes_per_entry = samples
+            .iter()
+            .max_by_key(|s| s.entries)
+            .map_or(0.0, MemorySample::bytes_per_entry);
+
+        Self {
+            allocations_per_op: allocations as f64 / operations,
+            bytes_allocated_per_op: replay_bytes as f64 / operations,
+            bytes_allocated,
+            bytes_per_entry,
+        }
+    }
+
+    // Every point of BENCH_MATRIX on a fresh `implementation`
+    pub fn measure(implementation: &CacheImplementation) -> Self {
+        let samples = BENCH_MATRIX
+            .iter()
+            .map(|point| MemorySample::measure(implementation, point))
+            .collect::<Vec<_>>();
+
+        Self::from_samples(&samples)
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "allocations/op: {:.3}, bytes allocated/op: {:.1}, bytes allocated: {}, bytes/entry: {:.1}",
+            self.allocations_per_op,
+            self.bytes_allocated_per_op,
+            self.bytes_allocated,
+            self.bytes_per_entry
+        )
+    }
+}
+
+// Keyed by implementation name, which bench_util's update-db uses as the `impl` tag of each point
+pub fn memory_reports<'a>(
+    implementations: impl Iterator<Item = &'a CacheImplementation>,
+) -> BTreeMap<&'a str, MemoryReport> {
+    implementations
+        .map(|implementation| (implementation.name, MemoryReport::measure(implementation)))
+        .collect()
+}
+
+pub fn write_memory_reports(path: &Path, reports: &BTreeMap<&str, MemoryReport>) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(writer, reports).map_err(io::Error::other)
+}
@@ -8,3 +8,21 @@ mod variables_range;
 pub use variables_range::*;
 
 pub mod macros;
+
+mod workload;
+pub use workload::*;
+
+mod fixtures;
+pub use fixtures::*;
+
+mod model;
+pub use model::*;
+
+mod bench_matrix;
+pub use bench_matrix::*;
+
+mod latency;
+pub use latency::*;
+
+mod memory;
+pub use memory::*;
@@ -0,0 +1,177 @@
+use crate::{Cache, RemovableCache, SizedCache};
+
+// Reference models for the state-machine tests: linear scans over a Vec, slow but
+// simple enough to be obviously right.
+
+// LRU, entries ordered from least to most recently used
+#[derive(Debug, Clone)]
+pub struct LRUModel {
+    entries: Vec<(i32, i32)>, // (key, value)
+    capacity: usize,
+}
+
+impl LRUModel {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // moves the key to the most recently used end
+    fn touch(&mut self, key: i32) -> Option<&mut (i32, i32)> {
+        let index = self.entries.iter().position(|&(k, _)| k == key)?;
+
+        let entry = self.entries.remove(index);
+        self.entries.push(entry);
+
+        self.entries.last_mut()
+    }
+}
+
+impl Cache for LRUModel {
+    fn put(&mut self, key: i32, value: i32) {
+        if let Some(entry) = self.touch(key) {
+            entry.1 = value;
+            return;
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            self.entries.remove(0);
+        }
+        self.entries.push((key, value));
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        self.touch(*key).map(|&mut (_, value)| value)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LFUEntry {
+    key: i32,
+    value: i32,
+    freq: usize,
+    last_access: usize,
+}
+
+// LFU, ties broken by least recent access
+#[derive(Debug, Clone)]
+pub struct LFUModel {
+    entries: Vec<LFUEntry>,
+    capacity: usize,
+    time: usize,
+}
+
+impl LFUModel {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            time: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn touch(&mut self, key: i32) -> Option<&mut LFUEntry> {
+        self.time += 1;
+
+        let entry = self.entries.iter_mut().find(|entry| entry.key == key)?;
+        entry.freq += 1;
+        entry.last_access = self.time;
+
+        Some(entry)
+    }
+}
+
+impl Cache for LFUModel {
+    fn put(&mut self, key: i32, value: i32) {
+        if let Some(entry) = self.touch(key) {
+            entry.value = value;
+            return;
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            let victim = (0..self.entries.len())
+                .min_by_key(|&i| (self.entries[i].freq, self.entries[i].last_access))
+                .unwrap();
+            self.entries.remove(victim);
+        }
+
+        self.entries.push(LFUEntry {
+            key,
+            value,
+            freq: 1,
+            last_access: self.time,
+        });
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        self.touch(*key).map(|entry| entry.value)
+    }
+}
+
+impl SizedCache for LRUModel {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.entries.iter().any(|&(k, _)| k == *key)
+    }
+}
+
+impl RemovableCache for LRUModel {
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        let index = self.entries.iter().position(|&(k, _)| k == *key)?;
+        Some(self.entries.remove(index).1)
+    }
+}
+
+impl SizedCache for LFUModel {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        self.entries.iter().any(|entry| entry.key == *key)
+    }
+}
+
+impl RemovableCache for LFUModel {
+    fn remove(&mut self, key: &i32) -> Option<i32> {
+        let index = self.entries.iter().position(|entry| entry.key == *key)?;
+        Some(self.entries.remove(index).value)
+    }
+}
@@ -0,0 +1,254 @@
+use super::CacheOperation;
+use super::variables_range::*;
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Zipf};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Environment variable read by OPERATIONS, e.g. CACHE_WORKLOAD="zipfian:0.99@0.1"
+pub const WORKLOAD_ENV: &str = "CACHE_WORKLOAD";
+
+// How keys are drawn from key_range()
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyDistribution {
+    Uniform,
+    // rank k is drawn with probability proportional to 1 / k^skew, key 0 is the hottest
+    Zipfian {
+        skew: f64,
+    },
+    // `hot_probability` of the accesses go to the first `hot_fraction` of the keys
+    Hotspot {
+        hot_fraction: f64,
+        hot_probability: f64,
+    },
+    // every key once, in order, then wrap around
+    Scan,
+    // cycle over the first `length` keys
+    Loop {
+        length: usize,
+    },
+    // uniform over a window of `size` keys, moved to fresh keys every `shift_every` operations
+    ShiftingWorkingSet {
+        size: usize,
+        shift_every: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Workload {
+    pub keys: KeyDistribution,
+    pub put_ratio: f64, // probability of a put, the rest are gets
+}
+
+impl Default for Workload {
+    // the original 50/50 put/get workload with uniform keys
+    fn default() -> Self {
+        Self::new(KeyDistribution::Uniform)
+    }
+}
+
+impl Workload {
+    pub fn new(keys: KeyDistribution) -> Self {
+        Self {
+            keys,
+            put_ratio: 0.5,
+        }
+    }
+
+    pub fn with_put_ratio(mut self, put_ratio: f64) -> Self {
+        self.put_ratio = put_ratio;
+        self
+    }
+
+    // the `index`-th operation of the sequence, stateful patterns depend on `index` only
+    pub fn operation<R: Rng + ?Sized>(&self, index: usize, rng: &mut R) -> CacheOperation {
+        let key = self.key(index, rng);
+
+        if rng.gen_bool(self.put_ratio) {
+            CacheOperation::Put {
+                key,
+                value: rng.gen_range(value_range()),
+            }
+        } else {
+            CacheOperation::Get { key }
+        }
+    }
+
+    pub fn operations(&self, seed: u64, len: usize) -> Vec<CacheOperation> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..len)
+            .map(|index| self.operation(index, &mut rng))
+            .collect()
+    }
+
+    // each operation gets its own seed, so shrinking removes operations one by one
+    pub fn strategy(self, len: RangeInclusive<usize>) -> BoxedStrategy<Vec<CacheOperation>> {
+        prop::collection::vec(any::<u64>(), len)
+            .prop_map(move |seeds| {
+                seeds
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, seed)| self.operation(index, &mut StdRng::seed_from_u64(seed)))
+                    .collect()
+            })
+            .boxed()
+    }
+
+    pub fn from_env() -> Option<Self> {
+        let spec = std::env::var(WORKLOAD_ENV).ok()?;
+
+        match spec.parse() {
+            Ok(workload) => Some(workload),
+            Err(err) => panic!("invalid {}=\"{}\": {}", WORKLOAD_ENV, spec, err),
+        }
+    }
+
+    fn key<R: Rng + ?Sized>(&self, index: usize, rng: &mut R) -> i32 {
+        let range = key_range();
+        let (start, num_keys) = (*range.start(), range.clone().count());
+
+        let offset = match self.keys {
+            KeyDistribution::Uniform => rng.gen_range(0..num_keys),
+            KeyDistribution::Zipfian { skew } => {
+                let zipf = Zipf::new(num_keys as u64, skew).expect("skew must be non-negative");
+                zipf.sample(rng) as usize - 1 // ranks start from 1
+            }
+            KeyDistribution::Hotspot {
+                hot_fraction,
+                hot_probability,
+            } => {
+                let num_hot_keys =
+                    ((num_keys as f64 * hot_fraction).ceil() as usize).clamp(1, num_keys);
+
+                if num_hot_keys == num_keys || rng.gen_bool(hot_probability) {
+                    rng.gen_range(0..num_hot_keys)
+                } else {
+                    rng.gen_range(num_hot_keys..num_keys)
+                }
+            }
+            KeyDistribution::Scan => index % num_keys,
+            KeyDistribution::Loop { length } => index % length.clamp(1, num_keys),
+            KeyDistribution::ShiftingWorkingSet { size, shift_every } => {
+                let size = size.clamp(1, num_keys);
+                let base = (index / shift_every.max(1)) * size;
+
+                (base + rng.gen_range(0..size)) % num_keys
+            }
+        };
+
+        start + offset as i32
+    }
+}
+
+// "name[:param[:param]][@put_ratio]", e.g. "uniform", "zipfian:0.99@0.1", "loop:1000"
+impl FromStr for Workload {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (pattern, put_ratio) = match spec.split_once('@') {
+            Some((pattern, put_ratio)) => (pattern, Some(put_ratio)),
+            None => (spec, None),
+        };
+
+        let mut parts = pattern.split(':');
+        let name = parts.next().unwrap_or_default().trim();
+        let params = parts.map(str::trim).collect::<Vec<_>>();
+
+        fn param<T: FromStr>(params: &[&str], index: usize, default: T) -> Result<T, String> {
+            match params.get(index) {
+                Some(param) => param
+                    .parse()
+                    .map_err(|_| format!("invalid parameter \"{}\"", param)),
+                None => Ok(default),
+            }
+        }
+
+        let keys = match name {
+            "uniform" => KeyDistribution::Uniform,
+            "zipfian" => KeyDistribution::Zipfian {
+                skew: param(&params, 0, 0.99)?,
+            },
+            "hotspot" => KeyDistribution::Hotspot {
+                hot_fraction: param(&params, 0, 0.2)?,
+                hot_probability: param(&params, 1, 0.8)?,
+            },
+            "scan" => KeyDistribution::Scan,
+            "loop" => KeyDistribution::Loop {
+                length: param(&params, 0, 1_000)?,
+            },
+            "shifting" => KeyDistribution::ShiftingWorkingSet {
+                size: param(&params, 0, 1_000)?,
+                shift_every: param(&params, 1, 1_000)?,
+            },
+            _ => return Err(format!("unknown workload \"{}\"", name)),
+        };
+
+        fn probability(name: &str, value: f64) -> Result<(), String> {
+            match (0.0..=1.0).contains(&value) {
+                true => Ok(()),
+                false => Err(format!("{} {} is not in [0, 1]", name, value)),
+            }
+        }
+
+        // what would otherwise panic when drawing keys
+        match keys {
+            KeyDistribution::Zipfian { skew } if !(skew.is_finite() && skew >= 0.0) => {
+                return Err(format!("skew {} is not a non-negative number", skew));
+            }
+            KeyDistribution::Hotspot {
+                hot_fraction,
+                hot_probability,
+            } => {
+                probability("hot fraction", hot_fraction)?;
+                probability("hot probability", hot_probability)?;
+            }
+            _ => {}
+        }
+
+        let mut workload = Workload::new(keys);
+        if let Some(put_ratio) = put_ratio {
+            workload.put_ratio = param(&[put_ratio], 0, 0.5)?;
+        }
+        probability("put ratio", workload.put_ratio)?;
+
+        Ok(workload)
+    }
+}
+
+impl Arbitrary for Workload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        let keys = prop_oneof![
+            Just(KeyDistribution::Uniform),
+            (0.5..=1.5f64).prop_map(|skew| KeyDistribution::Zipfian { skew }),
+            (0.01..=0.5f64, 0.5..=0.99f64).prop_map(|(hot_fraction, hot_probability)| {
+                KeyDistribution::Hotspot {
+                    hot_fraction,
+                    hot_probability,
+                }
+            }),
+            Just(KeyDistribution::Scan),
+            (1..=1_000usize).prop_map(|length| KeyDistribution::Loop { length }),
+            (1..=1_000usize, 1..=200usize).prop_map(|(size, shift_every)| {
+                KeyDistribution::ShiftingWorkingSet { size, shift_every }
+            }),
+        ];
+
+        (keys, 0.0..=1.0f64)
+            .prop_map(|(keys, put_ratio)| Workload { keys, put_ratio })
+            .boxed()
+    }
+}
+
+// Picks a workload first, then a sequence of operations drawn from it
+pub fn workload_operations_strategy(
+    len: RangeInclusive<usize>,
+) -> impl Strategy<Value = (Workload, Vec<CacheOperation>)> {
+    any::<Workload>()
+        .prop_flat_map(move |workload| (Just(workload), workload.strategy(len.clone())))
+}
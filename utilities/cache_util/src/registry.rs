@@ -0,0 +1,226 @@
+use crate::{Cache, SizedCache};
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// The LeetCode-style API every q146/q460 implementation exposes, object safe so that
+// tests, benches and tools can hold any of them behind a Box
+pub trait LeetCodeCache {
+    fn get(&mut self, key: i32) -> i32;
+    fn put(&mut self, key: i32, value: i32);
+    fn len(&self) -> usize;
+    fn contains(&self, key: i32) -> bool;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // no-op unless the implementation's crate is built with its validate feature
+    fn debug_validate(&self) {}
+
+    // None unless the implementation's crate is built with its dump feature
+    #[cfg(feature = "dump")]
+    fn dump(&self) -> Option<crate::StructureDump> {
+        None
+    }
+}
+
+// so that replay(), RecordingCache and check_recording() take any of them, -1 being a miss
+impl<C: LeetCodeCache + ?Sized> Cache for C {
+    fn put(&mut self, key: i32, value: i32) {
+        LeetCodeCache::put(self, key, value);
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        Some(LeetCodeCache::get(self, *key)).filter(|&value| value != -1)
+    }
+}
+
+impl<C: LeetCodeCache + ?Sized> SizedCache for C {
+    fn len(&self) -> usize {
+        LeetCodeCache::len(self)
+    }
+
+    fn contains(&self, key: &i32) -> bool {
+        LeetCodeCache::contains(self, *key)
+    }
+}
+
+// A registered variant, e.g. { problem: "q146", name: "d_ary_heap_4_eviction", .. }
+pub struct CacheImplementation {
+    pub problem: &'static str,
+    pub name: &'static str,
+    pub module: &'static str, // module_path!() of the register_caches! call
+    pub new: fn(i32) -> Box<dyn LeetCodeCache>,
+}
+
+impl CacheImplementation {
+    // "q146/d_ary_heap_4_eviction"
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.problem, self.name)
+    }
+}
+
+// Called once per solution module, implements LeetCodeCache for each listed type and
+// defines the module's `IMPLEMENTATIONS`:
+//
+//     cache_util::register_caches!("q146";
+//         "d_ary_heap_4" => LRUCache<4>,
+//         "d_ary_heap_4_eviction" => LRUEvictionCache<4>,
+//     );
+//
+// debug_validate() and dump() forward to the inherent ones when the calling crate has a
+// validate or dump feature.
+#[macro_export]
+macro_rules! register_caches {
+    ($problem:literal; $($name:literal => $ty:ty),+ $(,)?) => {
+        $(
+            impl $crate::LeetCodeCache for $ty {
+                fn get(&mut self, key: i32) -> i32 {
+                    <$ty>::get(self, key)
+                }
+
+                fn put(&mut self, key: i32, value: i32) {
+                    <$ty>::put(self, key, value)
+                }
+
+                fn len(&self) -> usize {
+                    <$ty>::len(self)
+                }
+
+                fn contains(&self, key: i32) -> bool {
+                    <$ty>::contains(self, key)
+                }
+
+                #[cfg(feature = "validate")]
+                fn debug_validate(&self) {
+                    <$ty>::debug_validate(self)
+                }
+
+                #[cfg(feature = "dump")]
+                fn dump(&self) -> Option<$crate::StructureDump> {
+                    Some(<$ty>::dump(self))
+                }
+            }
+        )+
+
+        pub const IMPLEMENTATIONS: &[$crate::CacheImplementation] = &[
+            $(
+                $crate::CacheImplementation {
+                    problem: $problem,
+                    name: $name,
+                    module: module_path!(),
+                    new: |capacity| Box::new(<$ty>::new(capacity)),
+                },
+            )+
+        ];
+    };
+}
+
+// The modules under `src_dir` that call register_caches! but have no variant in
+// `implementations`, i.e. that a crate's hand-written implementations() leaves out
+pub fn unregistered_modules<'a>(
+    src_dir: impl AsRef<Path>,
+    implementations: impl IntoIterator<Item = &'a CacheImplementation>,
+) -> io::Result<Vec<String>> {
+    let registered = implementations
+        .into_iter()
+        .filter_map(|implementation| implementation.module.rsplit("::").next())
+        .collect::<BTreeSet<_>>();
+
+    let mut unregistered = Vec::new();
+    for entry in fs::read_dir(src_dir)? {
+        let path = entry?.path();
+        let Some(module) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        let registers = fs::read_to_string(&path)?.lines().any(|line| {
+            let line = line.trim_start();
+            !line.starts_with("//") && line.contains("register_caches!(")
+        });
+        if registers && !registered.contains(module) {
+            unregistered.push(module.to_string());
+        }
+    }
+
+    unregistered.sort();
+    Ok(unregistered)
+}
+
+// The methods every GenericCache or EvictionCache wrapper forwards to its `$cache` field, called
+// in the wrapper's impl block next to the LeetCode new(), get() and put():
+//
+//     impl LRUCache {
+//         // new(), get(), put()
+//
+//         cache_util::forward_cache_methods!(cache);
+//     }
+//
+// `batch` adds from_iter(), extend(), get_many() and put_many(), for the caches whose policy
+// fixes its heap once per batch.
+#[macro_export]
+macro_rules! forward_cache_methods {
+    ($cache:ident) => {
+        pub fn len(&self) -> usize {
+            self.$cache.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.$cache.is_empty()
+        }
+
+        pub fn contains(&self, key: i32) -> bool {
+            self.$cache.contains(&key)
+        }
+
+        pub fn inspect(&self, key: i32) -> Option<$crate::KeyMetadata> {
+            self.$cache.inspect(&key)
+        }
+
+        // the next `n` keys to be evicted, first one first
+        pub fn victims(&self, n: usize) -> Vec<i32> {
+            self.$cache.victims(n)
+        }
+
+        #[cfg(feature = "validate")]
+        pub fn debug_validate(&self) {
+            $crate::DebugValidate::debug_validate(&self.$cache);
+        }
+
+        #[cfg(feature = "dump")]
+        pub fn dump(&self) -> $crate::StructureDump {
+            $crate::Dump::dump(&self.$cache)
+        }
+    };
+    ($cache:ident, batch) => {
+        $crate::forward_cache_methods!($cache);
+
+        // new() then extend()
+        pub fn from_iter(capacity: i32, items: impl IntoIterator<Item = (i32, i32)>) -> Self {
+            let mut cache = Self::new(capacity);
+            cache.extend(items);
+            cache
+        }
+
+        // as put() one by one would, but heapified once instead of sifting up each new key
+        pub fn extend(&mut self, items: impl IntoIterator<Item = (i32, i32)>) {
+            $crate::Cache::extend(&mut self.$cache, items);
+        }
+
+        // get() for each key, -1 on a miss, with the heap fixed once for the whole batch
+        pub fn get_many(&mut self, keys: &[i32]) -> Vec<i32> {
+            $crate::Cache::get_many(&mut self.$cache, keys)
+                .into_iter()
+                .map(|value| value.unwrap_or(-1))
+                .collect()
+        }
+
+        // put() for each item, with the heap fixed once per batch between evictions
+        pub fn put_many(&mut self, items: impl IntoIterator<Item = (i32, i32)>) {
+            $crate::Cache::put_many(&mut self.$cache, items);
+        }
+    };
+}
@@ -0,0 +1,33 @@
+// Binary indexed tree over 0..len, point updates and prefix sums in O(log n)
+pub struct FenwickTree {
+    tree: Vec<i64>, // 1-based internally
+}
+
+impl FenwickTree {
+    pub fn new(len: usize) -> Self {
+        Self {
+            tree: vec![0; len + 1],
+        }
+    }
+
+    pub fn add(&mut self, index: usize, delta: i64) {
+        let mut i = index + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // sum of 0..end
+    pub fn prefix_sum(&self, end: usize) -> i64 {
+        let mut sum = 0;
+
+        let mut i = end;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+
+        sum
+    }
+}
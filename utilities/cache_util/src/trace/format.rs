@@ -0,0 +1,275 @@
+use crate::CacheOperation;
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+// Environment variable read by OPERATIONS, a trace path whose format is guessed from the extension
+pub const TRACE_ENV: &str = "CACHE_TRACE";
+
+const BINARY_MAGIC: &[u8; 4] = b"CTRC";
+const BINARY_VERSION: u8 = 1;
+
+// The most gets an ARC trace expands to, ~1.5 GB of operations; each line is checked before it
+// is expanded, so that a huge block count fails instead of filling the memory
+pub const MAX_ARC_OPERATIONS: u64 = 1 << 27;
+
+const TAG_GET: u8 = 0;
+const TAG_PUT: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    // one access per line: "key", "get,key[,result]" or "put,key,value", comma or whitespace separated
+    Text,
+    // ARC traces: "start_block num_blocks ignore request_id", expanded to one get per block, at
+    // most MAX_ARC_OPERATIONS in all
+    Arc,
+    // LIRS traces: one block per line, "*" marker lines are skipped
+    Lirs,
+    // "CTRC", version byte, then per operation a tag byte and little-endian i32 key (and value)
+    Binary,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "text" | "csv" => Ok(TraceFormat::Text),
+            "arc" => Ok(TraceFormat::Arc),
+            "lirs" => Ok(TraceFormat::Lirs),
+            "bin" | "binary" => Ok(TraceFormat::Binary),
+            _ => Err(format!("unknown trace format \"{}\"", name)),
+        }
+    }
+}
+
+impl TraceFormat {
+    // guesses from the file extension, falls back to Text
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("arc") => TraceFormat::Arc,
+            Some("lirs") => TraceFormat::Lirs,
+            Some("bin") | Some("ctrc") => TraceFormat::Binary,
+            _ => TraceFormat::Text,
+        }
+    }
+}
+
+fn invalid_data(line_no: usize, msg: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_no, msg),
+    )
+}
+
+fn parse_field<T: FromStr>(line_no: usize, field: &str) -> io::Result<T> {
+    field
+        .parse()
+        .map_err(|_| invalid_data(line_no, format!("invalid number \"{}\"", field)))
+}
+
+fn block_to_key(line_no: usize, block: u64) -> io::Result<i32> {
+    i32::try_from(block)
+        .map_err(|_| invalid_data(line_no, format!("block {} does not fit in a key", block)))
+}
+
+fn read_text<R: BufRead>(reader: R) -> io::Result<Vec<CacheOperation>> {
+    let mut operations = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let (line_no, line) = (index + 1, line?);
+
+        let fields = line
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect::<Vec<_>>();
+
+        let operation = match fields.as_slice() {
+            [] => continue,
+            [first, ..] if first.starts_with('#') => continue,
+            [key] => CacheOperation::Get {
+                key: parse_field(line_no, key)?,
+            },
+            // the third field is the result in RecordingCache logs
+            [op, key] | [op, key, _] if op.eq_ignore_ascii_case("get") => CacheOperation::Get {
+                key: parse_field(line_no, key)?,
+            },
+            [op, key, value] if op.eq_ignore_ascii_case("put") => CacheOperation::Put {
+                key: parse_field(line_no, key)?,
+                value: parse_field(line_no, value)?,
+            },
+            _ => return Err(invalid_data(line_no, format!("unexpected \"{}\"", line))),
+        };
+
+        operations.push(operation);
+    }
+
+    Ok(operations)
+}
+
+fn read_arc<R: BufRead>(reader: R) -> io::Result<Vec<CacheOperation>> {
+    let mut operations = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let (line_no, line) = (index + 1, line?);
+
+        let mut fields = line.split_whitespace();
+        let (Some(start), Some(count)) = (fields.next(), fields.next()) else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Err(invalid_data(line_no, format!("unexpected \"{}\"", line)));
+        };
+
+        let start: u64 = parse_field(line_no, start)?;
+        let count: u64 = parse_field(line_no, count)?;
+
+        if operations.len() as u64 + count > MAX_ARC_OPERATIONS {
+            let blocks = format!("expands past {} operations", MAX_ARC_OPERATIONS);
+            return Err(invalid_data(line_no, blocks));
+        }
+        let end = start
+            .checked_add(count)
+            .filter(|&end| end <= i32::MAX as u64 + 1)
+            .ok_or_else(|| {
+                let blocks = format!("blocks {} + {} do not fit in keys", start, count);
+                invalid_data(line_no, blocks)
+            })?;
+
+        for block in start..end {
+            operations.push(CacheOperation::Get {
+                key: block_to_key(line_no, block)?,
+            });
+        }
+    }
+
+    Ok(operations)
+}
+
+fn read_lirs<R: BufRead>(reader: R) -> io::Result<Vec<CacheOperation>> {
+    let mut operations = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let (line_no, line) = (index + 1, line?);
+
+        let line = line.trim();
+        if line.is_empty() || line == "*" {
+            continue;
+        }
+
+        operations.push(CacheOperation::Get {
+            key: block_to_key(line_no, parse_field(line_no, line)?)?,
+        });
+    }
+
+    Ok(operations)
+}
+
+fn read_le_i32(rest: &mut &[u8]) -> io::Result<i32> {
+    let (field, tail) = rest
+        .split_first_chunk::<4>()
+        .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+
+    *rest = tail;
+    Ok(i32::from_le_bytes(*field))
+}
+
+fn read_binary<R: Read>(mut reader: R) -> io::Result<Vec<CacheOperation>> {
+    let mut header = [0u8; 5];
+    reader.read_exact(&mut header)?;
+
+    if &header[..4] != BINARY_MAGIC || header[4] != BINARY_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a version 1 binary trace",
+        ));
+    }
+
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let mut operations = Vec::new();
+    let mut rest = bytes.as_slice();
+
+    while let Some((&tag, tail)) = rest.split_first() {
+        rest = tail;
+
+        let operation = match tag {
+            TAG_GET => CacheOperation::Get {
+                key: read_le_i32(&mut rest)?,
+            },
+            TAG_PUT => CacheOperation::Put {
+                key: read_le_i32(&mut rest)?,
+                value: read_le_i32(&mut rest)?,
+            },
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown operation tag {}", tag),
+                ));
+            }
+        };
+
+        operations.push(operation);
+    }
+
+    Ok(operations)
+}
+
+pub fn read_trace<R: BufRead>(reader: R, format: TraceFormat) -> io::Result<Vec<CacheOperation>> {
+    match format {
+        TraceFormat::Text => read_text(reader),
+        TraceFormat::Arc => read_arc(reader),
+        TraceFormat::Lirs => read_lirs(reader),
+        TraceFormat::Binary => read_binary(reader),
+    }
+}
+
+pub fn load_trace(path: impl AsRef<Path>, format: TraceFormat) -> io::Result<Vec<CacheOperation>> {
+    read_trace(BufReader::new(File::open(path)?), format)
+}
+
+pub fn trace_from_env() -> Option<Vec<CacheOperation>> {
+    let path = std::env::var(TRACE_ENV).ok()?;
+
+    match load_trace(&path, TraceFormat::from_path(path.as_ref())) {
+        Ok(operations) => Some(operations),
+        Err(err) => panic!("invalid {}=\"{}\": {}", TRACE_ENV, path, err),
+    }
+}
+
+pub fn write_text_trace<W: Write>(mut writer: W, operations: &[CacheOperation]) -> io::Result<()> {
+    for operation in operations {
+        match operation {
+            CacheOperation::Put { key, value } => writeln!(writer, "put,{},{}", key, value)?,
+            CacheOperation::Get { key } => writeln!(writer, "get,{}", key)?,
+        }
+    }
+
+    writer.flush()
+}
+
+pub fn write_binary_trace<W: Write>(writer: W, operations: &[CacheOperation]) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+
+    writer.write_all(BINARY_MAGIC)?;
+    writer.write_all(&[BINARY_VERSION])?;
+
+    for operation in operations {
+        match operation {
+            CacheOperation::Put { key, value } => {
+                writer.write_all(&[TAG_PUT])?;
+                writer.write_all(&key.to_le_bytes())?;
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            CacheOperation::Get { key } => {
+                writer.write_all(&[TAG_GET])?;
+                writer.write_all(&key.to_le_bytes())?;
+            }
+        }
+    }
+
+    writer.flush()
+}
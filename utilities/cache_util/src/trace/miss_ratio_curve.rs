@@ -0,0 +1,203 @@
+use super::fenwick_tree::FenwickTree;
+use crate::CacheOperation;
+
+use std::collections::HashMap;
+
+// LRU miss ratio for every capacity, from one pass of Mattson's stack-distance algorithm.
+//
+// Every operation references its key, the same as `replay(.., fill_on_miss = true)`:
+// hits and misses are counted over gets, puts only move the key to the top of the stack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissRatioCurve {
+    hits: Vec<f64>, // hits[c] = gets hitting in an LRU cache of capacity c, non-decreasing
+    gets: f64,
+}
+
+impl MissRatioCurve {
+    pub fn exact(operations: &[CacheOperation]) -> Self {
+        let mut analyzer = StackDistances::new(operations.len());
+
+        let mut histogram = Vec::new();
+        let mut gets = 0.0;
+
+        for operation in operations {
+            let (key, is_get) = key_of(operation);
+
+            let distance = analyzer.access(key);
+            if is_get {
+                gets += 1.0;
+                record(&mut histogram, distance, 1.0);
+            }
+        }
+
+        Self::from_histogram(histogram, gets)
+    }
+
+    // SHARDS (Waldspurger et al., FAST '15): only keys whose hash falls under `rate` are
+    // tracked, their distances and counts are scaled by 1 / rate. Memory and time shrink
+    // with `rate`. Capacities well above 1 / rate are usually within a few percent, smaller
+    // ones depend on whether the few hottest keys made it into the sample.
+    pub fn shards(operations: &[CacheOperation], rate: f64) -> Self {
+        assert!(rate > 0.0 && rate <= 1.0, "sampling rate must be in (0, 1]");
+
+        let threshold = (rate * SHARDS_MODULUS as f64) as u64;
+        let sampled = |key: i32| hash(key) % SHARDS_MODULUS < threshold;
+
+        let num_sampled = operations
+            .iter()
+            .filter(|operation| sampled(key_of(operation).0))
+            .count();
+        let mut analyzer = StackDistances::new(num_sampled);
+
+        let mut histogram = Vec::new();
+        let (mut gets, mut sampled_gets) = (0.0, 0.0);
+
+        for operation in operations {
+            let (key, is_get) = key_of(operation);
+            gets += if is_get { 1.0 } else { 0.0 };
+
+            if !sampled(key) {
+                continue;
+            }
+
+            let distance = analyzer.access(key);
+            if is_get {
+                sampled_gets += 1.0;
+
+                let scaled =
+                    distance.map(|distance| ((distance as f64 / rate).round() as usize).max(1));
+                record(&mut histogram, scaled, 1.0 / rate);
+            }
+        }
+
+        // SHARDS-adj: the sample rarely has exactly `rate` of the gets, put the difference
+        // in the smallest distance so that the curve still ends at the right miss ratio
+        if let Some(first) = histogram.get_mut(1) {
+            *first += gets - sampled_gets / rate;
+        }
+
+        Self::from_histogram(histogram, gets)
+    }
+
+    fn from_histogram(histogram: Vec<f64>, gets: f64) -> Self {
+        let mut hits = Vec::with_capacity(histogram.len().max(1));
+        hits.push(0.0);
+
+        let mut sum = 0.0;
+        for count in histogram.into_iter().skip(1) {
+            sum += count;
+            hits.push(sum);
+        }
+
+        Self { hits, gets }
+    }
+
+    pub fn gets(&self) -> f64 {
+        self.gets
+    }
+
+    // smallest capacity from which a larger cache doesn't help, i.e. the working set size
+    pub fn max_capacity(&self) -> usize {
+        self.hits.len() - 1
+    }
+
+    pub fn hit_ratio(&self, capacity: usize) -> f64 {
+        if self.gets == 0.0 {
+            return 0.0;
+        }
+
+        self.to_hit_ratio(self.hits[capacity.min(self.max_capacity())])
+    }
+
+    pub fn miss_ratio(&self, capacity: usize) -> f64 {
+        1.0 - self.to_hit_ratio(self.hits[capacity.min(self.max_capacity())])
+    }
+
+    // (capacity, miss ratio) for capacities 0..=max_capacity()
+    pub fn points(&self) -> impl Iterator<Item = (usize, f64)> + '_ {
+        (0..=self.max_capacity()).map(|capacity| (capacity, self.miss_ratio(capacity)))
+    }
+
+    // smallest capacity reaching `miss_ratio`, None if even max_capacity() doesn't
+    pub fn capacity_for(&self, miss_ratio: f64) -> Option<usize> {
+        let capacity = self
+            .hits
+            .partition_point(|&hits| 1.0 - self.to_hit_ratio(hits) > miss_ratio);
+
+        (capacity <= self.max_capacity()).then_some(capacity)
+    }
+
+    // no gets count as neither hits nor misses
+    fn to_hit_ratio(&self, hits: f64) -> f64 {
+        if self.gets == 0.0 {
+            return 1.0;
+        }
+
+        (hits / self.gets).clamp(0.0, 1.0)
+    }
+}
+
+const SHARDS_MODULUS: u64 = 1 << 24;
+
+// splitmix64 finalizer, keys are often sequential block numbers
+fn hash(key: i32) -> u64 {
+    let mut x = key as u32 as u64;
+
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
+
+fn key_of(operation: &CacheOperation) -> (i32, bool) {
+    match *operation {
+        CacheOperation::Put { key, .. } => (key, false),
+        CacheOperation::Get { key } => (key, true),
+    }
+}
+
+// histogram[d] += weight, cold misses (None) are not recorded
+fn record(histogram: &mut Vec<f64>, distance: Option<usize>, weight: f64) {
+    let Some(distance) = distance else {
+        return;
+    };
+
+    if histogram.len() <= distance {
+        histogram.resize(distance + 1, 0.0);
+    }
+    histogram[distance] += weight;
+}
+
+// Marks the latest reference of every key in a Fenwick tree over time, so the number of
+// distinct keys referenced since a key's previous reference is a range sum.
+struct StackDistances {
+    last_access: HashMap<i32, usize>,
+    marks: FenwickTree,
+    time: usize,
+}
+
+impl StackDistances {
+    fn new(len: usize) -> Self {
+        Self {
+            last_access: HashMap::new(),
+            marks: FenwickTree::new(len),
+            time: 0,
+        }
+    }
+
+    // 1-based stack distance, None on the first reference
+    fn access(&mut self, key: i32) -> Option<usize> {
+        let time = self.time;
+        self.time += 1;
+
+        let distance = self.last_access.insert(key, time).map(|last| {
+            let between = self.marks.prefix_sum(time) - self.marks.prefix_sum(last + 1);
+            self.marks.add(last, -1);
+
+            between as usize + 1
+        });
+
+        self.marks.add(time, 1);
+
+        distance
+    }
+}
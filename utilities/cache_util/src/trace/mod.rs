@@ -0,0 +1,13 @@
+mod format;
+pub use format::*;
+
+mod replay;
+pub use replay::*;
+
+mod fenwick_tree;
+
+mod miss_ratio_curve;
+pub use miss_ratio_curve::*;
+
+mod recording;
+pub use recording::*;
@@ -0,0 +1,246 @@
+use crate::{Cache, CacheOperation};
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// A logged operation with what the cache returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedOperation {
+    Put { key: i32, value: i32 },
+    Get { key: i32, result: Option<i32> },
+}
+
+impl RecordedOperation {
+    pub fn operation(&self) -> CacheOperation {
+        match *self {
+            RecordedOperation::Put { key, value } => CacheOperation::Put { key, value },
+            RecordedOperation::Get { key, .. } => CacheOperation::Get { key },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub capacity: Option<usize>,
+    pub operations: Vec<RecordedOperation>,
+}
+
+impl Recording {
+    pub fn cache_operations(&self) -> Vec<CacheOperation> {
+        self.operations
+            .iter()
+            .map(RecordedOperation::operation)
+            .collect()
+    }
+}
+
+// Wraps a cache and logs every call in the text trace format, with the result of gets
+// as a third field ("-" on a miss), so the log also loads as a TraceFormat::Text trace:
+//
+//     # capacity 2
+//     put,1,10
+//     get,1,10
+//     get,2,-
+//
+// Write errors can't surface through Cache, the first one is kept and returned by finish().
+pub struct RecordingCache<C: Cache, W: Write = BufWriter<File>> {
+    cache: C,
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<C: Cache> RecordingCache<C> {
+    pub fn create(cache: C, capacity: usize, path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(
+            cache,
+            capacity,
+            BufWriter::new(File::create(path)?),
+        ))
+    }
+}
+
+impl<C: Cache, W: Write> RecordingCache<C, W> {
+    pub fn new(cache: C, capacity: usize, writer: W) -> Self {
+        let mut recording = Self {
+            cache,
+            writer,
+            error: None,
+        };
+
+        recording.log(format_args!("# capacity {}", capacity));
+        recording
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.cache
+    }
+
+    // flushes the log and hands back the cache
+    pub fn finish(mut self) -> io::Result<C> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        self.writer.flush()?;
+        Ok(self.cache)
+    }
+
+    fn log(&mut self, line: fmt::Arguments) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = writeln!(self.writer, "{}", line) {
+            self.error = Some(err);
+        }
+    }
+}
+
+impl<C: Cache, W: Write> Cache for RecordingCache<C, W> {
+    fn put(&mut self, key: i32, value: i32) {
+        self.cache.put(key, value);
+        self.log(format_args!("put,{},{}", key, value));
+    }
+
+    fn get(&mut self, key: &i32) -> Option<i32> {
+        let result = self.cache.get(key);
+
+        match result {
+            Some(value) => self.log(format_args!("get,{},{}", key, value)),
+            None => self.log(format_args!("get,{},-", key)),
+        }
+
+        result
+    }
+}
+
+fn invalid_data(line_no: usize, msg: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_no, msg),
+    )
+}
+
+fn parse_field(line_no: usize, field: &str) -> io::Result<i32> {
+    field
+        .parse()
+        .map_err(|_| invalid_data(line_no, format!("invalid number \"{}\"", field)))
+}
+
+// "# capacity N", other comments are ignored
+fn parse_capacity(line_no: usize, comment: &str) -> io::Result<Option<usize>> {
+    let Some(capacity) = comment.trim().strip_prefix("capacity") else {
+        return Ok(None);
+    };
+
+    capacity
+        .trim()
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid_data(line_no, format!("invalid capacity \"{}\"", capacity)))
+}
+
+pub fn read_recording<R: BufRead>(reader: R) -> io::Result<Recording> {
+    let mut recording = Recording::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let (line_no, line) = (index + 1, line?);
+        let line = line.trim();
+
+        if let Some(comment) = line.strip_prefix('#') {
+            recording.capacity = parse_capacity(line_no, comment)?.or(recording.capacity);
+            continue;
+        }
+
+        let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+
+        let operation = match fields.as_slice() {
+            [""] => continue,
+            ["put", key, value] => RecordedOperation::Put {
+                key: parse_field(line_no, key)?,
+                value: parse_field(line_no, value)?,
+            },
+            ["get", key, "-"] => RecordedOperation::Get {
+                key: parse_field(line_no, key)?,
+                result: None,
+            },
+            ["get", key, value] => RecordedOperation::Get {
+                key: parse_field(line_no, key)?,
+                result: Some(parse_field(line_no, value)?),
+            },
+            _ => return Err(invalid_data(line_no, format!("unexpected \"{}\"", line))),
+        };
+
+        recording.operations.push(operation);
+    }
+
+    Ok(recording)
+}
+
+pub fn load_recording(path: impl AsRef<Path>) -> io::Result<Recording> {
+    read_recording(BufReader::new(File::open(path)?))
+}
+
+// every *.log file in `dir`, sorted by path
+pub fn load_recordings(dir: impl AsRef<Path>) -> io::Result<Vec<(PathBuf, Recording)>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "log") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| load_recording(&path).map(|recording| (path, recording)))
+        .collect()
+}
+
+// The first get whose result differs from the recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    pub index: usize, // into Recording::operations
+    pub key: i32,
+    pub recorded: Option<i32>,
+    pub actual: Option<i32>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "operation #{} get({}): recorded {:?}, got {:?}",
+            self.index, self.key, self.recorded, self.actual
+        )
+    }
+}
+
+// Re-runs `recording` against `cache`, which should be fresh and have the recorded capacity
+pub fn check_recording<C: Cache + ?Sized>(
+    cache: &mut C,
+    recording: &Recording,
+) -> Result<(), Divergence> {
+    for (index, operation) in recording.operations.iter().enumerate() {
+        match *operation {
+            RecordedOperation::Put { key, value } => cache.put(key, value),
+            RecordedOperation::Get { key, result } => {
+                let actual = cache.get(&key);
+
+                if actual != result {
+                    return Err(Divergence {
+                        index,
+                        key,
+                        recorded: result,
+                        actual,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
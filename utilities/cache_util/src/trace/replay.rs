@@ -0,0 +1,95 @@
+use crate::{Cache, CacheOperation};
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub gets: usize,
+    pub hits: usize,
+    pub puts: usize, // including the ones issued on misses when filling
+    pub elapsed: Duration,
+}
+
+impl ReplayReport {
+    pub fn misses(&self) -> usize {
+        self.gets - self.hits
+    }
+
+    pub fn operations(&self) -> usize {
+        self.gets + self.puts
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        if self.gets == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / self.gets as f64
+    }
+
+    pub fn miss_ratio(&self) -> f64 {
+        if self.gets == 0 {
+            return 0.0;
+        }
+
+        1.0 - self.hit_ratio()
+    }
+
+    pub fn ops_per_sec(&self) -> f64 {
+        self.operations() as f64 / self.elapsed.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "gets: {}, hits: {}, misses: {}, puts: {}, hit ratio: {:.4}, elapsed: {:?}, ops/s: {:.0}",
+            self.gets,
+            self.hits,
+            self.misses(),
+            self.puts,
+            self.hit_ratio(),
+            self.elapsed,
+            self.ops_per_sec()
+        )
+    }
+}
+
+// Runs `operations` against `cache` in order.
+//
+// Block traces only contain reads, so with `fill_on_miss` a missed get is followed by
+// a put of the key (value = key), the way a read-through cache would load it.
+pub fn replay<C: Cache + ?Sized>(
+    cache: &mut C,
+    operations: &[CacheOperation],
+    fill_on_miss: bool,
+) -> ReplayReport {
+    let mut report = ReplayReport::default();
+
+    let start = Instant::now();
+    for operation in operations {
+        match *operation {
+            CacheOperation::Put { key, value } => {
+                cache.put(key, value);
+                report.puts += 1;
+            }
+            CacheOperation::Get { key } => {
+                report.gets += 1;
+
+                match cache.get(&key) {
+                    Some(_) => report.hits += 1,
+                    None if fill_on_miss => {
+                        cache.put(key, key);
+                        report.puts += 1;
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+    report.elapsed = start.elapsed();
+
+    report
+}
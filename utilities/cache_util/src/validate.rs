@@ -0,0 +1,5 @@
+// Structural invariant checks, panicking on the first violation. Meant to be called after
+// every operation in tests, so a corruption is reported at the step that causes it.
+pub trait DebugValidate {
+    fn debug_validate(&self);
+}
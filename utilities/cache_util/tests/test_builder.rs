@@ -0,0 +1,165 @@
+use proptest::prelude::*;
+use rstest::rstest;
+use std::time::Duration;
+
+use cache_util::*;
+
+// (policy, structure) for every combination build() accepts, each with both storages
+const STRUCTURES: &[(&str, &str)] = &[
+    ("lru", "priority_queue"),
+    ("lru", "vec_hashmap"),
+    ("lru", "d_ary_heap_4"),
+    ("lru", "d_ary_heap_8"),
+    ("lru", "pairing_heap"),
+    ("lfu", "priority_queue"),
+    ("lfu", "vec_hashmap"),
+    ("lfu", "d_ary_heap_4"),
+    ("lfu", "d_ary_heap_8"),
+    ("lfu", "pairing_heap"),
+    ("lfu", "freq_buckets"),
+];
+
+fn model(policy: &str, capacity: usize) -> Box<dyn RemovableCache> {
+    match policy {
+        "lru" => Box::new(LRUModel::new(capacity)),
+        _ => Box::new(LFUModel::new(capacity)),
+    }
+}
+
+#[test]
+fn test_built_caches_remove() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, steps) in removal_steps_strategy())| {
+        for (&(policy, structure), storage) in STRUCTURES.iter().flat_map(|s| [(s, "hashmap"), (s, "policy")]) {
+            let mut model = model(policy, capacity);
+            let mut built = CacheBuilder::new(policy, capacity)
+                .structure(structure)
+                .storage(storage)
+                .build_removable()
+                .unwrap();
+
+            for step in &steps {
+                match *step {
+                    RemovalStep::Get(key) => {
+                        prop_assert_eq!(built.get(&key), model.get(&key), "{}/{}/{} on get({})", policy, structure, storage, key);
+                    }
+                    RemovalStep::Put(key, value) => {
+                        built.put(key, value);
+                        model.put(key, value);
+                    }
+                    RemovalStep::Remove(key) => {
+                        prop_assert_eq!(built.remove(&key), model.remove(&key), "{}/{}/{} on remove({})", policy, structure, storage, key);
+                    }
+                }
+                prop_assert_eq!(built.len(), model.len(), "{}/{}/{} len", policy, structure, storage);
+            }
+        }
+    });
+}
+
+#[rstest]
+#[case(CacheBuilder::new("lru", 0), "capacity")]
+#[case(CacheBuilder::new("lru", MAX_CAPACITY + 1), "capacity")]
+#[case(CacheBuilder::new("lfu", usize::MAX), "capacity")]
+#[case(CacheBuilder::new("mru", 4), "policy")]
+#[case(CacheBuilder::new("lru", 4).structure("skip_list"), "structure")]
+#[case(CacheBuilder::new("lru", 4).structure("d_ary_heap_3"), "structure")]
+#[case(CacheBuilder::new("lru", 4).structure("freq_buckets"), "freq_buckets")]
+#[case(CacheBuilder::new("lru", 4).storage("btree"), "storage")]
+#[case(CacheBuilder::new("lru", 4).ttl(Duration::ZERO), "ttl")]
+fn test_builder_rejects(#[case] builder: CacheBuilder, #[case] mentions: &str) {
+    let err = builder.build().err().expect("should be rejected");
+    assert!(
+        err.contains(mentions),
+        "\"{}\" should mention {}",
+        err,
+        mentions
+    );
+}
+
+// the largest capacity builds without reserving room for all of it
+#[test]
+fn test_builder_max_capacity() {
+    for storage in ["hashmap", "policy"] {
+        let mut cache = CacheBuilder::new("lru", MAX_CAPACITY)
+            .storage(storage)
+            .ttl(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        cache.put(1, 1);
+        assert_eq!(cache.get(&1), Some(1));
+    }
+}
+
+#[test]
+fn test_builder_from_config() {
+    let json = r#"{"policy": "lru", "structure": "pairing_heap", "capacity": 2, "stats": true}"#;
+    let toml = "policy = \"lru\"\nstructure = \"pairing_heap\"\ncapacity = 2\nstats = true\n";
+
+    let config = CacheConfig::from_json(json).unwrap();
+    assert_eq!(CacheConfig::from_toml(toml).unwrap(), config);
+    assert_eq!(config.storage, "hashmap");
+    assert_eq!(config.ttl_ms, None);
+
+    let builder = CacheBuilder::from_config(config);
+    let mut cache = builder.build().unwrap();
+    cache.put(1, 1);
+    cache.put(2, 2);
+    cache.get(&1);
+    cache.put(3, 3);
+    assert_eq!(cache.get(&2), None);
+    assert_eq!(cache.get(&1), Some(1));
+    assert!(builder.stats_handle().is_some());
+
+    assert!(CacheConfig::from_json(r#"{"policy": "lru"}"#).is_err());
+    assert!(CacheConfig::from_json(r#"{"policy": "lru", "capacity": -1}"#).is_err());
+    assert!(CacheConfig::from_toml("policy = \"lru\"\ncapacity = 2\nsize = 3\n").is_err());
+}
+
+#[test]
+fn test_builder_stats() {
+    let builder = CacheBuilder::new("lru", 2).stats(true);
+    let stats = builder.stats_handle().unwrap();
+    let mut cache = builder.build().unwrap();
+
+    cache.put(1, 1);
+    cache.put(2, 2);
+    cache.put(3, 3);
+    assert_eq!(
+        cache.get_many(&[1, 2, 3, 4]),
+        vec![None, Some(2), Some(3), None]
+    );
+
+    assert_eq!((stats.puts(), stats.hits(), stats.misses()), (3, 2, 2));
+    assert_eq!(
+        (stats.evictions(), stats.size(), stats.capacity()),
+        (1, 2, 2)
+    );
+    assert_eq!(stats.hit_ratio(), 0.5);
+
+    stats.reset();
+    assert_eq!(stats.gets(), 0);
+    assert!(CacheBuilder::new("lru", 2).stats_handle().is_none());
+}
+
+#[test]
+fn test_builder_ttl() {
+    let builder = CacheBuilder::new("lru", 2).stats(true);
+    let stats = builder.stats_handle().unwrap();
+
+    let mut cache = builder
+        .clone()
+        .ttl(Duration::from_millis(1))
+        .build()
+        .unwrap();
+    cache.put(1, 1);
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(cache.get(&1), None);
+
+    let mut cache = builder.ttl(Duration::from_secs(3600)).build().unwrap();
+    cache.put(1, 1);
+    assert_eq!(cache.get(&1), Some(1));
+
+    // expired reads count as misses, and both caches count into the same handle
+    assert_eq!((stats.puts(), stats.hits(), stats.misses()), (2, 1, 1));
+}
@@ -0,0 +1,109 @@
+use cache_util::*;
+
+#[test]
+fn test_get_or_load() {
+    let stats = CacheStats::default();
+    let mut cache = InstrumentedCache::new(Box::new(LRUModel::new(2)), 2, stats.clone());
+
+    assert_eq!(cache.get_or_load(1, |key| Ok::<_, ()>(key * 10)), Ok(10));
+    assert_eq!(cache.get_or_load(1, |_| Err("loaded twice")), Ok(10));
+    assert_eq!(
+        cache.get_or_load(2, |_| Err("unavailable")),
+        Err("unavailable")
+    );
+    assert_eq!(cache.get(&2), None);
+
+    assert_eq!((stats.hits(), stats.misses(), stats.puts()), (1, 3, 1));
+    assert_eq!(stats.load_failures(), 1);
+}
+
+#[test]
+fn test_metrics_render() {
+    let registry = MetricsRegistry::new();
+
+    registry.register("sessions", CacheStats::default());
+    registry.register("quote\"d", CacheStats::default());
+
+    // the builder's handle, registered under the name it is scraped by
+    let builder = CacheBuilder::new("lru", 2).stats(true);
+    let users = builder.stats_handle().unwrap();
+    registry.register("users", users);
+    let mut cache = builder.build().unwrap();
+    for key in 0..3 {
+        cache.put(key, key);
+    }
+    cache.get(&0);
+    cache.get(&2);
+
+    let text = registry.render();
+    for line in [
+        "# HELP cache_hits_total Gets that found the key.",
+        "# TYPE cache_hits_total counter",
+        "cache_hits_total{cache=\"users\"} 1",
+        "cache_misses_total{cache=\"users\"} 1",
+        "cache_evictions_total{cache=\"users\"} 1",
+        "cache_puts_total{cache=\"users\"} 3",
+        "cache_load_failures_total{cache=\"users\"} 0",
+        "# TYPE cache_size gauge",
+        "cache_size{cache=\"users\"} 2",
+        "cache_capacity{cache=\"users\"} 2",
+        "cache_hits_total{cache=\"sessions\"} 0",
+        "cache_hits_total{cache=\"quote\\\"d\"} 0",
+    ] {
+        assert!(
+            text.lines().any(|l| l == line),
+            "no \"{}\" in\n{}",
+            line,
+            text
+        );
+    }
+
+    // by name, within each metric
+    let order = text
+        .lines()
+        .filter(|l| l.starts_with("cache_hits_total"))
+        .collect::<Vec<_>>();
+    assert_eq!(order.len(), 3);
+    assert!(order[1].contains("sessions") && order[2].contains("users"));
+
+    assert!(registry.unregister("sessions").is_some());
+    assert_eq!(registry.names(), vec!["quote\"d", "users"]);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_metrics_server() {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::sync::Arc;
+
+    fn request(server: &MetricsServer, line: &str) -> String {
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        write!(stream, "{}\r\nHost: localhost\r\n\r\n", line).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    let registry = Arc::new(MetricsRegistry::new());
+    let stats = CacheStats::default();
+    stats.record_get(true);
+    registry.register("users", stats);
+
+    let server = MetricsServer::bind("127.0.0.1:0", registry.clone()).unwrap();
+
+    let response = request(&server, "GET /metrics HTTP/1.1");
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"), "{}", head);
+    assert!(head.contains(METRICS_CONTENT_TYPE), "{}", head);
+    assert_eq!(body, registry.render());
+    assert!(body.contains("cache_hits_total{cache=\"users\"} 1"));
+
+    assert!(request(&server, "GET /other HTTP/1.1").starts_with("HTTP/1.1 404"));
+    assert!(request(&server, "POST /metrics HTTP/1.1").starts_with("HTTP/1.1 405"));
+
+    let addr = server.local_addr();
+    server.shutdown();
+    assert!(TcpStream::connect(addr).is_err());
+}
@@ -0,0 +1,61 @@
+use proptest::prelude::*;
+use rstest::rstest;
+
+use cache_util::*;
+
+fn replayed_miss_ratio(capacity: usize, operations: &[CacheOperation]) -> f64 {
+    let mut cache = LRUModel::new(capacity);
+
+    replay(&mut cache, operations, true).miss_ratio()
+}
+
+#[test]
+fn test_miss_ratio_curve_small_trace() {
+    let operations = [1, 2, 3, 1, 2, 4, 1]
+        .map(|key| CacheOperation::Get { key })
+        .to_vec();
+
+    // stack distances: -, -, -, 3, 3, -, 3
+    let curve = MissRatioCurve::exact(&operations);
+
+    assert_eq!(curve.max_capacity(), 3);
+    assert_eq!(curve.miss_ratio(2), 1.0);
+    assert_eq!(curve.miss_ratio(3), 4.0 / 7.0);
+    assert_eq!(curve.miss_ratio(100), 4.0 / 7.0);
+    assert_eq!(curve.capacity_for(0.6), Some(3));
+    assert_eq!(curve.capacity_for(0.5), None);
+}
+
+#[test]
+fn test_miss_ratio_curve_matches_replay() {
+    let provider = mock_operations_range_provider_default();
+
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((_workload, operations) in workload_operations_strategy(provider.operations_range()))| {
+        let curve = MissRatioCurve::exact(&operations);
+
+        for capacity in 1..=curve.max_capacity() + 1 {
+            prop_assert_eq!(curve.miss_ratio(capacity), replayed_miss_ratio(capacity, &operations));
+        }
+
+        // sampling every key is exact
+        prop_assert_eq!(MissRatioCurve::shards(&operations, 1.0), curve);
+    });
+}
+
+#[rstest]
+#[case(KeyDistribution::Zipfian { skew: 0.99 })]
+#[case(KeyDistribution::Hotspot { hot_fraction: 0.05, hot_probability: 0.9 })]
+#[case(KeyDistribution::Loop { length: 5_000 })]
+fn test_shards_approximation(#[case] keys: KeyDistribution) {
+    let operations = Workload::new(keys).operations(SEED, 200_000);
+
+    let exact = MissRatioCurve::exact(&operations);
+    let approx = MissRatioCurve::shards(&operations, 0.1);
+
+    // small capacities are dominated by a few hot keys, which the sample either has or misses
+    for capacity in [1_000, 10_000, 50_000] {
+        let error = (exact.miss_ratio(capacity) - approx.miss_ratio(capacity)).abs();
+        assert!(error < 0.05, "capacity {}: error {}", capacity, error);
+    }
+}
@@ -0,0 +1,140 @@
+use proptest::prelude::*;
+use rstest::rstest;
+
+use cache_util::*;
+
+fn gets(keys: &[i32]) -> Vec<CacheOperation> {
+    keys.iter()
+        .map(|&key| CacheOperation::Get { key })
+        .collect()
+}
+
+#[rstest]
+#[case(TraceFormat::Text, "# comment\n1\nget,2\n\nput 3 30\nGET, 4\n", vec![
+    CacheOperation::Get { key: 1 },
+    CacheOperation::Get { key: 2 },
+    CacheOperation::Put { key: 3, value: 30 },
+    CacheOperation::Get { key: 4 },
+])]
+#[case(TraceFormat::Arc, "10 3 0 1\n\n2 1 0 2\n", gets(&[10, 11, 12, 2]))]
+#[case(TraceFormat::Lirs, "5\n*\n7\n 5 \n", gets(&[5, 7, 5]))]
+fn test_read_trace(
+    #[case] format: TraceFormat,
+    #[case] input: &str,
+    #[case] expected: Vec<CacheOperation>,
+) {
+    assert_eq!(read_trace(input.as_bytes(), format).unwrap(), expected);
+}
+
+#[rstest]
+#[case(TraceFormat::Text, "put,1")]
+#[case(TraceFormat::Text, "get,x")]
+#[case(TraceFormat::Arc, "1")]
+#[case(TraceFormat::Arc, "18446744073709551615 2")]
+#[case(TraceFormat::Arc, "2147483647 2")]
+#[case(TraceFormat::Arc, "0 4294967296")]
+#[case(TraceFormat::Lirs, "4294967296")]
+#[case(TraceFormat::Binary, "CTRC\x02")]
+#[case(TraceFormat::Binary, "CTRC\x01\x01\x00")]
+fn test_read_invalid_trace(#[case] format: TraceFormat, #[case] input: &str) {
+    assert!(read_trace(input.as_bytes(), format).is_err());
+}
+
+// blocks that fit in keys, but 2^31 gets: rejected before any is expanded
+#[test]
+fn test_read_arc_huge_count() {
+    let err = read_trace("0 2147483648 0 1\n".as_bytes(), TraceFormat::Arc).unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("expands past"), "{}", err);
+}
+
+#[test]
+fn test_replay_fill_on_miss() {
+    let operations = gets(&[1, 2, 1, 3, 1, 2]);
+
+    // 1 and 2 are loaded, 1 hits, 3 evicts 2, 1 hits, 2 evicts 3
+    let report = replay(&mut LRUModel::new(2), &operations, true);
+
+    assert_eq!(
+        (report.gets, report.hits, report.misses(), report.puts),
+        (6, 2, 4, 4)
+    );
+    assert_eq!(report.hit_ratio(), 2.0 / 6.0);
+}
+
+#[test]
+fn test_replay_without_fill() {
+    let operations = vec![
+        CacheOperation::Put { key: 1, value: 10 },
+        CacheOperation::Get { key: 1 },
+        CacheOperation::Get { key: 2 },
+        CacheOperation::Get { key: 2 },
+    ];
+
+    let report = replay(&mut LRUModel::new(2), &operations, false);
+
+    assert_eq!((report.gets, report.hits, report.puts), (3, 1, 1));
+}
+
+#[test]
+fn test_trace_round_trip() {
+    let provider = mock_operations_range_provider_default();
+    let strategy = prop::collection::vec(any::<CacheOperation>(), provider.operations_range());
+
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |(operations in strategy)| {
+        let mut text = Vec::new();
+        write_text_trace(&mut text, &operations).unwrap();
+        prop_assert_eq!(read_trace(text.as_slice(), TraceFormat::Text).unwrap(), operations.clone());
+
+        let mut binary = Vec::new();
+        write_binary_trace(&mut binary, &operations).unwrap();
+        prop_assert_eq!(read_trace(binary.as_slice(), TraceFormat::Binary).unwrap(), operations);
+    });
+}
+
+#[test]
+fn test_recording_round_trip() {
+    let mut log = Vec::new();
+
+    let mut cache = RecordingCache::new(LRUModel::new(2), 2, &mut log);
+    cache.put(1, 10);
+    cache.put(2, 20);
+    assert_eq!(cache.get(&1), Some(10));
+    cache.put(3, 30);
+    assert_eq!(cache.get(&2), None);
+    cache.finish().unwrap();
+
+    let recording = read_recording(log.as_slice()).unwrap();
+    assert_eq!(recording.capacity, Some(2));
+    assert_eq!(
+        recording.operations[3..],
+        [
+            RecordedOperation::Put { key: 3, value: 30 },
+            RecordedOperation::Get {
+                key: 2,
+                result: None
+            },
+        ]
+    );
+
+    // the log is also a plain text trace
+    assert_eq!(
+        read_trace(log.as_slice(), TraceFormat::Text).unwrap(),
+        recording.cache_operations()
+    );
+
+    assert_eq!(check_recording(&mut LRUModel::new(2), &recording), Ok(()));
+
+    // a bigger cache still has key 2 at the last get
+    assert_eq!(
+        check_recording(&mut LRUModel::new(3), &recording),
+        Err(Divergence {
+            index: 4,
+            key: 2,
+            recorded: None,
+            actual: Some(20),
+        })
+    );
+}
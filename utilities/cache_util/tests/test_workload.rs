@@ -0,0 +1,157 @@
+use rstest::rstest;
+use std::collections::HashMap;
+
+use cache_util::*;
+
+fn keys(workload: Workload, len: usize) -> Vec<i32> {
+    workload
+        .operations(SEED, len)
+        .into_iter()
+        .map(|operation| match operation {
+            CacheOperation::Get { key } | CacheOperation::Put { key, .. } => key,
+        })
+        .collect()
+}
+
+fn counts(keys: &[i32]) -> HashMap<i32, usize> {
+    let mut counts = HashMap::new();
+    for &key in keys {
+        *counts.entry(key).or_default() += 1;
+    }
+    counts
+}
+
+#[rstest]
+#[case("uniform", KeyDistribution::Uniform, 0.5)]
+#[case("zipfian", KeyDistribution::Zipfian { skew: 0.99 }, 0.5)]
+#[case("zipfian:0@0.1", KeyDistribution::Zipfian { skew: 0.0 }, 0.1)]
+#[case(
+    "hotspot:0.1:0.9@1",
+    KeyDistribution::Hotspot { hot_fraction: 0.1, hot_probability: 0.9 },
+    1.0
+)]
+#[case("scan@0", KeyDistribution::Scan, 0.0)]
+#[case("loop:10", KeyDistribution::Loop { length: 10 }, 0.5)]
+#[case(
+    "shifting:100:50",
+    KeyDistribution::ShiftingWorkingSet { size: 100, shift_every: 50 },
+    0.5
+)]
+fn test_workload_parses(#[case] spec: &str, #[case] keys: KeyDistribution, #[case] put_ratio: f64) {
+    let expected = Workload::new(keys).with_put_ratio(put_ratio);
+    assert_eq!(spec.parse::<Workload>(), Ok(expected));
+}
+
+#[rstest]
+#[case("lognormal", "unknown workload")]
+#[case("loop:ten", "invalid parameter")]
+#[case("uniform@1.5", "put ratio")]
+#[case("uniform@nan", "put ratio")]
+#[case("zipfian:-1", "skew")]
+#[case("zipfian:nan", "skew")]
+#[case("zipfian:inf", "skew")]
+#[case("hotspot:1.5", "hot fraction")]
+#[case("hotspot:0.2:1.5", "hot probability")]
+#[case("hotspot:0.2:-0.1", "hot probability")]
+fn test_workload_rejects(#[case] spec: &str, #[case] mentions: &str) {
+    let err = spec.parse::<Workload>().unwrap_err();
+    assert!(
+        err.contains(mentions),
+        "{:?} doesn't mention {}",
+        err,
+        mentions
+    );
+}
+
+#[test]
+fn test_scan_visits_keys_in_order() {
+    let num_keys = key_range().count();
+    let keys = keys(Workload::new(KeyDistribution::Scan), num_keys + 10);
+
+    let expected = key_range().chain(key_range().take(10)).collect::<Vec<_>>();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn test_loop_cycles_over_its_keys() {
+    let keys = keys(Workload::new(KeyDistribution::Loop { length: 3 }), 7);
+
+    let start = *key_range().start();
+    let expected = [0, 1, 2, 0, 1, 2, 0].map(|offset| start + offset);
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn test_shifting_working_set_moves_its_window() {
+    let (size, shift_every) = (10, 100);
+    let workload = Workload::new(KeyDistribution::ShiftingWorkingSet { size, shift_every });
+    let keys = keys(workload, shift_every * 3);
+
+    let start = *key_range().start();
+    for (window, keys) in keys.chunks(shift_every).enumerate() {
+        let window_start = start + (window * size) as i32;
+        let window = window_start..window_start + size as i32;
+        assert!(
+            keys.iter().all(|key| window.contains(key)),
+            "{:?} outside {:?}",
+            keys,
+            window
+        );
+    }
+}
+
+#[test]
+fn test_hotspot_skews_to_hot_keys() {
+    let (hot_fraction, hot_probability) = (0.01, 0.9);
+    let workload = Workload::new(KeyDistribution::Hotspot {
+        hot_fraction,
+        hot_probability,
+    });
+    let keys = keys(workload, 10_000);
+
+    let num_hot_keys = (key_range().count() as f64 * hot_fraction).ceil() as i32;
+    let hot_keys = *key_range().start()..*key_range().start() + num_hot_keys;
+    let hot = keys.iter().filter(|key| hot_keys.contains(key)).count();
+
+    let share = hot as f64 / keys.len() as f64;
+    assert!(
+        (share - hot_probability).abs() < 0.02,
+        "hot share {}",
+        share
+    );
+}
+
+#[test]
+fn test_zipfian_skews_to_low_keys() {
+    let start = *key_range().start();
+    let top_share = |skew| {
+        let keys = keys(Workload::new(KeyDistribution::Zipfian { skew }), 10_000);
+        counts(&keys).get(&start).copied().unwrap_or_default() as f64 / keys.len() as f64
+    };
+
+    // with 10^5 keys, P(rank 1) is about 1 / H(10^5) = 8% for skew 1, and 10^-5 for skew 0
+    let (flat, skewed, steep) = (top_share(0.0), top_share(1.0), top_share(1.5));
+    assert!(
+        flat < 0.01,
+        "share of the hottest key with skew 0: {}",
+        flat
+    );
+    assert!(
+        (0.06..0.10).contains(&skewed),
+        "... with skew 1: {}",
+        skewed
+    );
+    assert!(steep > skewed, "... with skew 1.5: {}", steep);
+}
+
+#[test]
+fn test_put_ratio_controls_writes() {
+    let workload = Workload::new(KeyDistribution::Uniform).with_put_ratio(0.1);
+    let operations = workload.operations(SEED, 10_000);
+
+    let puts = operations
+        .iter()
+        .filter(|operation| matches!(operation, CacheOperation::Put { .. }))
+        .count();
+    assert!((800..1200).contains(&puts), "{} puts", puts);
+}
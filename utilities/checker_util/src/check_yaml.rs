@@ -13,12 +13,8 @@ pub fn check_yaml() {
         match fs::read_to_string(&file) {
             Ok(content) => {
                 let parse_result: Result<Value, Error> = serde_yaml::from_str(&content);
-                if parse_result.is_err() {
-                    eprintln!(
-                        "YAML parse error in {}:\n{}",
-                        file,
-                        parse_result.unwrap_err()
-                    );
+                if let Err(err) = parse_result {
+                    eprintln!("YAML parse error in {}:\n{}", file, err);
                     has_error = true;
                 }
             }
@@ -0,0 +1,292 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, Path, Token, Type};
+
+// #[derive(HeapNode)] writes PartialEq, Eq, PartialOrd, Ord and cache_util::HeapNodeTrait for
+// a heap node wrapping another one, from what each field is for:
+//
+//     #[derive(HeapNode)]
+//     #[heap_node(order_by(freq, node))] // by freq, then by the inner node, key ignored
+//     pub struct LFUHeapNode<H: HeapNodeTrait> {
+//         #[heap_node(access_count)]
+//         freq: i32,
+//         #[heap_node(inner)]
+//         node: H,
+//     }
+//
+// On the struct:
+//   order_by(a, b, ..)  the fields compared, in that order, smallest evicted first;
+//                       equality follows the same fields so that it agrees with Ord
+//   on_access = path    also calls `path(&mut self)` on each access, after the fields below
+// On a field:
+//   inner         the wrapped node, which gets the key, value and metadata no other field holds
+//   key / value   holds the key or value itself, the inner node getting () instead
+//   access_count  starts at 1 and counts accesses, reported by access_count()
+// Any other field starts as Default::default() and is left alone.
+#[proc_macro_derive(HeapNode, attributes(heap_node))]
+pub fn derive_heap_node(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Roles {
+    inner: Option<(Ident, Type)>,
+    key: Option<(Ident, Type)>,
+    value: Option<(Ident, Type)>,
+    access_count: Option<(Ident, Type)>,
+}
+
+impl Roles {
+    fn of(ident: &Ident, role: &Option<(Ident, Type)>) -> bool {
+        role.as_ref().is_some_and(|(field, _)| field == ident)
+    }
+}
+
+fn named_fields(input: &DeriveInput) -> syn::Result<&Punctuated<Field, Token![,]>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(&fields.named),
+            _ => Err(Error::new_spanned(
+                &input.ident,
+                "HeapNode needs named fields",
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            "HeapNode can only be derived for structs",
+        )),
+    }
+}
+
+// #[heap_node(order_by(..), on_access = ..)]
+fn struct_attributes(input: &DeriveInput) -> syn::Result<(Vec<Ident>, Option<Path>)> {
+    let mut order_by = None;
+    let mut on_access = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("heap_node"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("order_by") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let fields = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                order_by = Some(fields.into_iter().collect::<Vec<_>>());
+                Ok(())
+            } else if meta.path.is_ident("on_access") {
+                on_access = Some(meta.value()?.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `order_by(..)` or `on_access = ..`"))
+            }
+        })?;
+    }
+
+    match order_by {
+        Some(order_by) if !order_by.is_empty() => Ok((order_by, on_access)),
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            "HeapNode needs #[heap_node(order_by(..))] with at least one field",
+        )),
+    }
+}
+
+// #[heap_node(inner | key | value | access_count)], one role per field and field per role
+fn field_roles(fields: &Punctuated<Field, Token![,]>) -> syn::Result<Roles> {
+    let mut roles = Roles::default();
+
+    for field in fields {
+        let ident = field.ident.clone().expect("named fields have idents");
+        let mut has_role = false;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("heap_node"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let role = if meta.path.is_ident("inner") {
+                    &mut roles.inner
+                } else if meta.path.is_ident("key") {
+                    &mut roles.key
+                } else if meta.path.is_ident("value") {
+                    &mut roles.value
+                } else if meta.path.is_ident("access_count") {
+                    &mut roles.access_count
+                } else {
+                    return Err(meta.error("expected `inner`, `key`, `value` or `access_count`"));
+                };
+
+                if has_role {
+                    return Err(meta.error("a field can only have one role"));
+                }
+                if role.is_some() {
+                    return Err(meta.error("another field already has this role"));
+                }
+
+                *role = Some((ident.clone(), field.ty.clone()));
+                has_role = true;
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(roles)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = named_fields(input)?;
+    let (order_by, on_access) = struct_attributes(input)?;
+    let roles = field_roles(fields)?;
+
+    for ident in &order_by {
+        if !fields
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(ident))
+        {
+            return Err(Error::new_spanned(ident, "no such field"));
+        }
+    }
+
+    let krate = quote!(::cache_util);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // where the key and value live: their own field, else the inner node
+    let (key_type, key, inner_key) = match (&roles.key, &roles.inner) {
+        (Some((field, ty)), _) => (quote!(#ty), quote!(&self.#field), quote!(())),
+        (None, Some((inner, ty))) => (
+            quote!(<#ty as #krate::HeapNodeTrait>::Key),
+            quote!(#krate::HeapNodeTrait::key(&self.#inner)),
+            quote!(key),
+        ),
+        (None, None) => {
+            return Err(Error::new_spanned(
+                name,
+                "HeapNode needs a `key` field or an `inner` node",
+            ));
+        }
+    };
+    let (value_type, value, set_value, inner_value) = match (&roles.value, &roles.inner) {
+        (Some((field, ty)), _) => (
+            quote!(#ty),
+            quote!(&self.#field),
+            quote!(self.#field = value;),
+            quote!(()),
+        ),
+        (None, Some((inner, ty))) => (
+            quote!(<#ty as #krate::HeapNodeTrait>::Value),
+            quote!(#krate::HeapNodeTrait::value(&self.#inner)),
+            quote!(#krate::HeapNodeTrait::set_value(&mut self.#inner, value);),
+            quote!(value),
+        ),
+        (None, None) => {
+            return Err(Error::new_spanned(
+                name,
+                "HeapNode needs a `value` field or an `inner` node",
+            ));
+        }
+    };
+
+    let inits = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("named fields have idents");
+
+        if Roles::of(ident, &roles.key) {
+            quote!(#ident: key)
+        } else if Roles::of(ident, &roles.value) {
+            quote!(#ident: value)
+        } else if Roles::of(ident, &roles.inner) {
+            quote!(#ident: #krate::HeapNodeTrait::new(#inner_key, #inner_value))
+        } else if Roles::of(ident, &roles.access_count) {
+            quote!(#ident: 1)
+        } else {
+            quote!(#ident: ::core::default::Default::default())
+        }
+    });
+
+    let mut on_access_body = Vec::new();
+    if let Some((inner, _)) = &roles.inner {
+        on_access_body.push(quote!(#krate::HeapNodeTrait::on_access(&mut self.#inner);));
+    }
+    if let Some((count, _)) = &roles.access_count {
+        on_access_body.push(quote!(self.#count += 1;));
+    }
+    if let Some(hook) = &on_access {
+        on_access_body.push(quote!(#hook(self);));
+    }
+
+    let from_inner = |method: Ident| match &roles.inner {
+        Some((inner, _)) => quote!(#krate::HeapNodeTrait::#method(&self.#inner)),
+        None => quote!(::core::option::Option::None),
+    };
+    let access_count = match &roles.access_count {
+        Some((count, _)) => quote!(::core::option::Option::Some(self.#count as u64)),
+        None => from_inner(Ident::new("access_count", name.span())),
+    };
+    let last_access = from_inner(Ident::new("last_access", name.span()));
+    let inserted_at = from_inner(Ident::new("inserted_at", name.span()));
+
+    Ok(quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                true #(&& self.#order_by == other.#order_by)*
+            }
+        }
+        impl #impl_generics ::core::cmp::Eq for #name #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::cmp::PartialOrd for #name #ty_generics #where_clause {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+        impl #impl_generics ::core::cmp::Ord for #name #ty_generics #where_clause {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ordering::Equal
+                    #(.then_with(|| ::core::cmp::Ord::cmp(&self.#order_by, &other.#order_by)))*
+            }
+        }
+
+        impl #impl_generics #krate::HeapNodeTrait for #name #ty_generics #where_clause {
+            type Key = #key_type;
+            type Value = #value_type;
+
+            fn new(key: Self::Key, value: Self::Value) -> Self {
+                Self {
+                    #(#inits,)*
+                }
+            }
+
+            fn key(&self) -> &Self::Key {
+                #key
+            }
+            fn value(&self) -> &Self::Value {
+                #value
+            }
+            fn set_value(&mut self, value: Self::Value) {
+                #set_value
+            }
+
+            fn on_access(&mut self) {
+                #(#on_access_body)*
+            }
+
+            fn access_count(&self) -> ::core::option::Option<u64> {
+                #access_count
+            }
+            fn last_access(&self) -> ::core::option::Option<u64> {
+                #last_access
+            }
+            fn inserted_at(&self) -> ::core::option::Option<u64> {
+                #inserted_at
+            }
+        }
+    })
+}
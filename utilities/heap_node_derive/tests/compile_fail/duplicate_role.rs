@@ -0,0 +1,14 @@
+use cache_util::{HeapNode, HeapNodeTrait};
+
+#[derive(HeapNode)]
+#[heap_node(order_by(freq, node))]
+struct TwoInnerNodes<H: HeapNodeTrait> {
+    #[heap_node(access_count)]
+    freq: i32,
+    #[heap_node(inner)]
+    node: H,
+    #[heap_node(inner)]
+    other: H,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use cache_util::{HeapNode, HeapNodeTrait};
+
+#[derive(HeapNode)]
+struct Unordered<H: HeapNodeTrait> {
+    #[heap_node(access_count)]
+    freq: i32,
+    #[heap_node(inner)]
+    node: H,
+}
+
+fn main() {}
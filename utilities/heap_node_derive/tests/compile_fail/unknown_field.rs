@@ -0,0 +1,12 @@
+use cache_util::{HeapNode, HeapNodeTrait};
+
+#[derive(HeapNode)]
+#[heap_node(order_by(frequency, node))]
+struct Misspelled<H: HeapNodeTrait> {
+    #[heap_node(access_count)]
+    freq: i32,
+    #[heap_node(inner)]
+    node: H,
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+use proptest::prelude::*;
+
+use cache_util::*;
+
+// LFUHeapNode written with #[derive(HeapNode)] outside cache_util, plus an ignored field
+// bumped by an on_access hook
+#[derive(HeapNode)]
+#[heap_node(order_by(freq, node), on_access = DerivedLFUHeapNode::touch)]
+struct DerivedLFUHeapNode<H: HeapNodeTrait> {
+    #[heap_node(access_count)]
+    freq: i32,
+    #[heap_node(inner)]
+    node: H,
+    touched: u32,
+}
+
+impl<H: HeapNodeTrait> DerivedLFUHeapNode<H> {
+    fn touch(&mut self) {
+        self.touched += 1;
+        assert_eq!(
+            self.freq as u32,
+            self.touched + 1,
+            "hook runs after the fields"
+        );
+    }
+}
+
+#[test]
+fn test_derived_heap_node_matches_lfu_model() {
+    let config = ProptestConfig::with_cases(NUM_PROPTEST_CASES); // Number of test cases to generate
+    proptest!(config, |((capacity, operations) in evicting_operations_strategy())| {
+        let mut derived = GenericCache::new(
+            EvictionPolicyVHM::<DerivedLFUHeapNode<LRUHeapNode>>::new(),
+            HashMapStorage::new(capacity),
+            capacity,
+        );
+        let mut expected = LFUModel::new(capacity);
+
+        for operation in &operations {
+            match *operation {
+                CacheOperation::Put { key, value } => {
+                    derived.put(key, value);
+                    expected.put(key, value);
+                }
+                CacheOperation::Get { key } => {
+                    prop_assert_eq!(derived.get(&key), expected.get(&key), "get({})", key);
+                }
+            }
+        }
+    });
+}
+
+// the errors the derive reports instead of expanding, see tests/compile_fail/*.stderr
+#[test]
+fn test_derive_rejects() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/compile_fail/*.rs");
+}